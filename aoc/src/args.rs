@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// Command line arguments after the subcommand. "--name value" pairs are
+// options, a "--name" followed by another option (or nothing) is a flag and
+// everything else is positional. Subcommands say how many positional
+// arguments they take, any beyond that are rejected.
+pub struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, Option<String>>,
+}
+
+impl Args {
+    pub fn new(args: &[String], num_positional: usize) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+
+        let mut iter = args.iter().peekable();
+        while let Some(arg) = iter.next() {
+            match arg.strip_prefix("--") {
+                Some(name) => {
+                    let value = match iter.peek() {
+                        Some(next) if !next.starts_with("--") => {
+                            iter.next().cloned()
+                        }
+                        _ => None,
+                    };
+                    options.insert(name.to_string(), value);
+                }
+                None if positional.len() < num_positional => {
+                    positional.push(arg.clone())
+                }
+                None => return Err(format!("Unexpected argument {}", arg)),
+            }
+        }
+
+        Ok(Args {
+            positional,
+            options,
+        })
+    }
+
+    pub fn positional(&self, idx: usize) -> Option<&str> {
+        self.positional.get(idx).map(String::as_str)
+    }
+
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).and_then(|value| value.as_deref())
+    }
+
+    pub fn parse<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        match self.get(name) {
+            Some(value) => value.parse().map(Some).map_err(|_| {
+                format!("Invalid value for --{}: {}", name, value)
+            }),
+            None if self.has_flag(name) => {
+                Err(format!("Missing value for --{}", name))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn require<T: FromStr>(&self, name: &str) -> Result<T, String> {
+        self.parse(name)?
+            .ok_or_else(|| format!("Missing required option --{}", name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> Args {
+        let args: Vec<String> =
+            ["file.txt", "--year", "2022", "--redact", "--day", "x"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect();
+        Args::new(&args, 1).unwrap()
+    }
+
+    #[test]
+    fn test_args() {
+        let args = get_test_input();
+        assert_eq!(args.positional(0), Some("file.txt"));
+        assert_eq!(args.positional(1), None);
+        assert!(args.has_flag("redact"));
+        assert!(!args.has_flag("tag"));
+        assert_eq!(args.require::<u32>("year"), Ok(2022));
+        assert!(args.require::<u32>("day").is_err());
+        assert!(args.require::<u32>("redact").is_err());
+        assert_eq!(args.parse::<u32>("tag"), Ok(None));
+    }
+
+    #[test]
+    fn test_args_unexpected_argument() {
+        assert!(Args::new(&["2022".to_string()], 0).is_err());
+        let args = ["a.txt".to_string(), "b.txt".to_string()];
+        assert!(Args::new(&args[..1], 1).is_ok());
+        assert_eq!(
+            Args::new(&args, 1).err(),
+            Some("Unexpected argument b.txt".to_string())
+        );
+    }
+}
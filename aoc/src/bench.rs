@@ -0,0 +1,159 @@
+use super::alloc::allocations;
+use super::args::Args;
+use super::{repo_root, year_dir, year_solutions};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, PartialEq)]
+pub struct BenchRecord {
+    pub timestamp: u64,
+    pub year: u32,
+    pub day: u32,
+    pub time: Duration,
+    pub allocations: usize,
+}
+
+impl BenchRecord {
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{}",
+            self.timestamp,
+            self.year,
+            self.day,
+            self.time.as_nanos(),
+            self.allocations
+        )
+    }
+
+    fn from_csv(line: &str) -> Option<Self> {
+        match line.split(',').collect::<Vec<&str>>()[..] {
+            [timestamp, year, day, nanos, allocations] => Some(BenchRecord {
+                timestamp: timestamp.parse().ok()?,
+                year: year.parse().ok()?,
+                day: day.parse().ok()?,
+                time: Duration::from_nanos(nanos.parse().ok()?),
+                allocations: allocations.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub fn history_path() -> PathBuf {
+    repo_root().join("bench_history.csv")
+}
+
+pub fn read_history(path: &Path) -> Result<Vec<BenchRecord>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| {
+            BenchRecord::from_csv(line).ok_or(format!(
+                "Invalid benchmark record on line {} of {}",
+                idx + 1,
+                path.display()
+            ))
+        })
+        .collect()
+}
+
+// Run each selected day once in-process and append its timing and
+// allocation count to the history
+pub fn main(args: &Args) -> Result<(), String> {
+    let year: u32 = args.require("year")?;
+    let day: Option<u32> = args.parse("day")?;
+
+    let solutions = year_solutions(year)?;
+
+    // Solutions read their inputs relative to the year's directory
+    env::set_current_dir(year_dir(year)?)
+        .map_err(|err| format!("Could not enter year directory: {}", err))?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+
+    let mut records = Vec::new();
+    for solution in solutions {
+        if day.map_or(true, |day| day == solution.day) {
+            let start_allocations = allocations();
+            let start = Instant::now();
+            (solution.main)();
+            let time = start.elapsed();
+
+            records.push(BenchRecord {
+                timestamp,
+                year,
+                day: solution.day,
+                time,
+                allocations: allocations() - start_allocations,
+            });
+        }
+    }
+
+    let path = history_path();
+    let mut history = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|err| format!("Could not open {}: {}", path.display(), err))?;
+
+    for record in records {
+        println!(
+            "{} day{:02}: {} ({} allocations)",
+            record.year,
+            record.day,
+            format_duration(record.time),
+            record.allocations
+        );
+        writeln!(history, "{}", record.to_csv())
+            .map_err(|err| format!("Could not write history: {}", err))?;
+    }
+
+    Ok(())
+}
+
+pub fn format_duration(time: Duration) -> String {
+    if time < Duration::from_millis(1) {
+        format!("{} us", time.as_micros())
+    } else if time < Duration::from_secs(1) {
+        format!("{:.1} ms", time.as_secs_f64() * 1000.0)
+    } else {
+        format!("{:.2} s", time.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bench_record_csv() {
+        let record = BenchRecord {
+            timestamp: 1671000000,
+            year: 2022,
+            day: 4,
+            time: Duration::from_nanos(1234567),
+            allocations: 2001,
+        };
+        assert_eq!(record.to_csv(), "1671000000,2022,4,1234567,2001");
+        assert_eq!(BenchRecord::from_csv(&record.to_csv()), Some(record));
+        assert_eq!(BenchRecord::from_csv("1671000000,2022,4,1234567"), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(15)), "15 us");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.5 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+    }
+}
@@ -0,0 +1,92 @@
+use super::args::Args;
+use super::YEARS;
+use common::solution::Solution;
+
+const NUM_DAYS: u32 = 25;
+const DAYS_PER_ROW: u32 = 5;
+
+pub fn main(args: &Args) -> Result<(), String> {
+    let year: Option<u32> = args.parse("year")?;
+
+    let mut found = false;
+    for (curr_year, solutions) in YEARS {
+        if year.map_or(true, |year| year == curr_year) {
+            println!("{}", render_calendar(curr_year, solutions));
+            found = true;
+        }
+    }
+
+    if found {
+        Ok(())
+    } else {
+        Err(format!("No solutions for year {}", year.unwrap()))
+    }
+}
+
+fn render_calendar(year: u32, solutions: &[Solution]) -> String {
+    let total: usize = solutions.iter().map(|x| x.stars()).sum();
+    let mut calendar = format!("{} ({}/{} stars)\n", year, total, 2 * NUM_DAYS);
+
+    for day in 1..=NUM_DAYS {
+        let stars = solutions
+            .iter()
+            .find(|x| x.day == day)
+            .map_or(0, |x| x.stars());
+        let cell = format!(
+            "{:>2} {}{}",
+            day,
+            "*".repeat(stars),
+            ".".repeat(2 - stars)
+        );
+
+        calendar.push_str(&cell);
+        if day % DAYS_PER_ROW == 0 {
+            calendar.push('\n');
+        } else {
+            calendar.push_str("   ");
+        }
+    }
+
+    calendar
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::solution::Tag;
+
+    fn get_test_input() -> Vec<Solution> {
+        vec![
+            Solution {
+                year: 2022,
+                day: 1,
+                title: "Calorie Counting",
+                tags: &[Tag::Parsing],
+                answers: [Some("24000"), Some("45000")],
+                main: || (),
+            },
+            Solution {
+                year: 2022,
+                day: 7,
+                title: "No Space Left On Device",
+                tags: &[Tag::Parsing],
+                answers: [Some("95437"), None],
+                main: || (),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_calendar() {
+        let calendar = render_calendar(2022, &get_test_input());
+        assert_eq!(
+            calendar,
+            "2022 (3/50 stars)\n\
+             \x201 **    2 ..    3 ..    4 ..    5 ..\n\
+             \x206 ..    7 *.    8 ..    9 ..   10 ..\n\
+             11 ..   12 ..   13 ..   14 ..   15 ..\n\
+             16 ..   17 ..   18 ..   19 ..   20 ..\n\
+             21 ..   22 ..   23 ..   24 ..   25 ..\n"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use super::args::Args;
+use std::fs;
+use year2021::day03::{
+    get_life_support, get_power_consumption, parse_diagnostic_report,
+    DiagnosticError, TiePolicy, TIE_POLICIES,
+};
+
+// Run the 2021 day03 diagnostics over a report with a tie policy picked by
+// name, defaulting to the puzzle's rule of preferring ones
+pub fn main(args: &Args) -> Result<(), String> {
+    let path = args.positional(0).ok_or("Missing diagnostic report")?;
+    let ties = args.get("ties").unwrap_or("prefer-1");
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Could not read {}: {}", path, err))?;
+
+    print!("{}", diagnose(&contents, ties)?);
+
+    Ok(())
+}
+
+fn diagnose(contents: &str, ties: &str) -> Result<String, String> {
+    let ties: TiePolicy = ties.parse().map_err(|_| {
+        format!(
+            "Unknown tie policy {}, expected one of: {}",
+            ties,
+            TIE_POLICIES.join(", ")
+        )
+    })?;
+
+    let lines: Vec<String> = contents.lines().map(|x| x.to_string()).collect();
+    let report = parse_diagnostic_report(&lines)
+        .map_err(|err| format!("Invalid diagnostic report: {}", err))?;
+
+    let format_err = |err| match err {
+        DiagnosticError::Tie { column } => {
+            format!("Tie in column {}", column + 1)
+        }
+        DiagnosticError::Empty => "Empty diagnostic report".to_string(),
+    };
+    let power = get_power_consumption(&report, ties).map_err(format_err)?;
+    let life_support = get_life_support(&report, ties).map_err(format_err)?;
+
+    Ok(format!(
+        "power consumption: {}\nlife support: {}\n",
+        power, life_support
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> &'static str {
+        "01\n11\n10\n01\n"
+    }
+
+    #[test]
+    fn test_diagnose() {
+        assert_eq!(
+            diagnose(get_test_input(), "prefer-0"),
+            Ok("power consumption: 2\nlife support: 3\n".to_string())
+        );
+        assert_eq!(
+            diagnose(get_test_input(), "error"),
+            Err("Tie in column 1".to_string())
+        );
+        assert!(diagnose(get_test_input(), "prefer-2").is_err());
+        assert_eq!(
+            diagnose("01\n1\n", "error"),
+            Err(
+                "Invalid diagnostic report: line 2: row length differs from \
+                 the first row"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            diagnose("01\n12\n", "error"),
+            Err("Invalid diagnostic report: line 2: invalid value".to_string())
+        );
+    }
+}
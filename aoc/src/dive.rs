@@ -0,0 +1,116 @@
+use super::args::Args;
+use std::fs;
+use year2021::day02::script::{Script, ScriptError};
+use year2021::day02::trajectory::{to_csv, to_svg};
+use year2021::day02::{command_model, Submarine, COMMAND_MODELS};
+
+// Run a 2021 day02 command script, or a plain command list, with one or more
+// comma separated command models picked by name. Trajectories are only
+// recorded when they are to be exported to compare the models.
+pub fn main(args: &Args) -> Result<(), String> {
+    let path = args.positional(0).ok_or("Missing command script")?;
+    let models: String = args.require("model")?;
+    let csv_path: Option<String> = args.parse("csv")?;
+    let svg_path: Option<String> = args.parse("svg")?;
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Could not read {}: {}", path, err))?;
+
+    let record = csv_path.is_some() || svg_path.is_some();
+    let mut subs = Vec::new();
+    for model in models.split(',') {
+        let sub = dive(&contents, model, record)?;
+        let product = sub.get_product().ok_or("Product overflowed")?;
+        println!("{}: {}", model, product);
+        subs.push((model, sub));
+    }
+
+    let profiles: Vec<_> = subs
+        .iter()
+        .filter_map(|(model, sub)| Some((*model, sub.trajectory()?)))
+        .collect();
+    if let Some(csv_path) = csv_path {
+        write_file(&csv_path, &to_csv(&profiles))?;
+    }
+    if let Some(svg_path) = svg_path {
+        write_file(&svg_path, &to_svg(&profiles))?;
+    }
+
+    Ok(())
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), String> {
+    fs::write(path, contents)
+        .map_err(|err| format!("Could not write {}: {}", path, err))
+}
+
+fn dive(
+    contents: &str,
+    model: &str,
+    record: bool,
+) -> Result<Submarine<i64>, String> {
+    let model = command_model(model).ok_or(format!(
+        "Unknown command model {}, expected one of: {}",
+        model,
+        COMMAND_MODELS.join(", ")
+    ))?;
+
+    let script: Script<i64> = contents
+        .parse()
+        .map_err(|err| format!("Invalid command script: {}", err))?;
+    let mut sub = Submarine::new(0, 0, 0, model);
+    if record {
+        sub.record();
+    }
+    script.run(&mut sub).map_err(|err| match err {
+        ScriptError::UndefinedVariable { name } => {
+            format!("Undefined variable {}", name)
+        }
+        ScriptError::UndefinedMacro { name } => {
+            format!("Undefined macro {}", name)
+        }
+        ScriptError::RecursionLimit { name } => {
+            format!("Too many nested calls to macro {}", name)
+        }
+        ScriptError::Rejected { index } => {
+            format!("Command {} rejected by the model", index + 1)
+        }
+    })?;
+
+    Ok(sub)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> &'static str {
+        "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n"
+    }
+
+    fn product(contents: &str, model: &str) -> Result<Option<i64>, String> {
+        dive(contents, model, false).map(|sub| sub.get_product())
+    }
+
+    #[test]
+    fn test_dive() {
+        assert_eq!(product(get_test_input(), "direct"), Ok(Some(150)));
+        assert_eq!(product(get_test_input(), "aim"), Ok(Some(900)));
+        assert!(product(get_test_input(), "drag").is_err());
+        assert_eq!(
+            product("down 1\nsideways 2\n", "aim"),
+            Err("Invalid command script: line 2, column 10: expected end \
+                 of statement"
+                .to_string())
+        );
+        assert_eq!(
+            product("let n = 4\nrepeat 2 {\n  down n\n  forward n\n}", "aim"),
+            Ok(Some(8 * 48))
+        );
+
+        // Only when asked, every command along with the starting state
+        let sub = dive(get_test_input(), "aim", true).unwrap();
+        assert_eq!(sub.trajectory().unwrap().states.len(), 7);
+        let sub = dive(get_test_input(), "aim", false).unwrap();
+        assert_eq!(sub.trajectory(), None);
+    }
+}
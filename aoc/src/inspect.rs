@@ -0,0 +1,441 @@
+use super::args::Args;
+use std::collections::HashMap;
+use std::fs;
+
+// Inputs with more groups than this are treated as repeated records (elves,
+// bingo boards) and summarized together rather than section by section
+const MAX_SECTIONS: usize = 5;
+const MAX_PROFILE_WIDTH: usize = 80;
+const MAX_FIELDS: usize = 10;
+
+pub fn main(args: &Args) -> Result<(), String> {
+    let path = args.positional(0).ok_or("Missing input file")?;
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Could not read {}: {}", path, err))?;
+
+    print!("{}", inspect(&contents));
+
+    Ok(())
+}
+
+struct Group<'a> {
+    // 1-based line number of the group's first line
+    start: usize,
+    lines: Vec<&'a str>,
+}
+
+fn split_groups<'a>(lines: &[&'a str]) -> Vec<Group<'a>> {
+    let mut groups: Vec<Group> = Vec::new();
+    let mut in_group = false;
+    for (idx, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            in_group = false;
+        } else if in_group {
+            groups.last_mut().unwrap().lines.push(*line);
+        } else {
+            groups.push(Group {
+                start: idx + 1,
+                lines: vec![*line],
+            });
+            in_group = true;
+        }
+    }
+
+    groups
+}
+
+// Minimum, maximum and most common line length
+fn length_stats(lines: &[&str]) -> Option<(usize, usize, usize)> {
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for line in lines {
+        *counts.entry(line.chars().count()).or_default() += 1;
+    }
+
+    let min = *counts.keys().min()?;
+    let max = *counts.keys().max()?;
+    let (modal, _) = counts
+        .iter()
+        .max_by_key(|(len, count)| (**count, std::cmp::Reverse(**len)))?;
+
+    Some((min, max, *modal))
+}
+
+fn char_class(c: char) -> char {
+    if c.is_ascii_digit() {
+        '9'
+    } else if c.is_lowercase() {
+        'a'
+    } else if c.is_uppercase() {
+        'A'
+    } else if c.is_whitespace() {
+        '_'
+    } else {
+        '.'
+    }
+}
+
+// One symbol per column: the class shared by every line long enough to have
+// that column, or '*' when lines disagree
+fn column_profile(lines: &[&str]) -> String {
+    let mut profile: Vec<Option<char>> = Vec::new();
+    for line in lines {
+        for (col, c) in line.chars().enumerate() {
+            let class = char_class(c);
+            match profile.get_mut(col) {
+                Some(Some(prev)) if *prev != class => *prev = '*',
+                Some(_) => (),
+                None => profile.push(Some(class)),
+            }
+        }
+    }
+
+    let mut profile: String = profile.into_iter().flatten().collect();
+    if profile.len() > MAX_PROFILE_WIDTH {
+        profile.truncate(MAX_PROFILE_WIDTH);
+        profile.push_str("...");
+    }
+
+    profile
+}
+
+// Integers in the line, in order. A '-' is only a sign when it does not
+// follow a digit or letter, so "2-4" is two numbers rather than 2 and -4.
+fn numeric_fields(line: &str) -> Vec<i64> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut fields = Vec::new();
+
+    let mut idx = 0;
+    while idx < chars.len() {
+        let negative = chars[idx] == '-'
+            && chars.get(idx + 1).is_some_and(|c| c.is_ascii_digit())
+            && (idx == 0 || !chars[idx - 1].is_alphanumeric());
+        if negative || chars[idx].is_ascii_digit() {
+            let start = idx;
+            idx += 1;
+            while idx < chars.len() && chars[idx].is_ascii_digit() {
+                idx += 1;
+            }
+
+            let field: String = chars[start..idx].iter().collect();
+            if let Ok(field) = field.parse() {
+                fields.push(field);
+            }
+        } else {
+            idx += 1;
+        }
+    }
+
+    fields
+}
+
+#[derive(Debug, PartialEq)]
+struct FieldRange {
+    min: i64,
+    max: i64,
+    // Number of lines with at least this many fields
+    count: usize,
+}
+
+fn field_ranges(lines: &[&str]) -> Vec<FieldRange> {
+    let mut ranges: Vec<FieldRange> = Vec::new();
+    for line in lines {
+        for (idx, field) in numeric_fields(line).into_iter().enumerate() {
+            match ranges.get_mut(idx) {
+                Some(range) => {
+                    range.min = range.min.min(field);
+                    range.max = range.max.max(field);
+                    range.count += 1;
+                }
+                None => ranges.push(FieldRange {
+                    min: field,
+                    max: field,
+                    count: 1,
+                }),
+            }
+        }
+    }
+
+    ranges
+}
+
+#[derive(Debug, PartialEq)]
+enum Grid {
+    Chars { rows: usize, cols: usize },
+    Numbers { rows: usize, cols: usize },
+}
+
+fn detect_grid(lines: &[&str]) -> Option<Grid> {
+    if lines.len() < 2 {
+        return None;
+    }
+
+    let cols = lines[0].chars().count();
+    let is_char_grid = cols >= 2
+        && lines.iter().all(|line| {
+            line.chars().count() == cols && !line.contains(char::is_whitespace)
+        });
+    if is_char_grid {
+        return Some(Grid::Chars {
+            rows: lines.len(),
+            cols,
+        });
+    }
+
+    let cols = lines[0].split_whitespace().count();
+    let is_number_grid = cols >= 2
+        && lines.iter().all(|line| {
+            let mut count = 0;
+            let all_numbers = line.split_whitespace().all(|x| {
+                count += 1;
+                x.parse::<i64>().is_ok()
+            });
+            all_numbers && count == cols
+        });
+    if is_number_grid {
+        return Some(Grid::Numbers {
+            rows: lines.len(),
+            cols,
+        });
+    }
+
+    None
+}
+
+fn format_grid(grid: &Grid) -> String {
+    match grid {
+        Grid::Chars { rows, cols } => format!("{}x{} characters", rows, cols),
+        Grid::Numbers { rows, cols } => format!("{}x{} numbers", rows, cols),
+    }
+}
+
+fn describe_lines(report: &mut String, lines: &[&str]) {
+    report.push_str(&format!("    columns: {}\n", column_profile(lines)));
+
+    let ranges = field_ranges(lines);
+    for (idx, range) in ranges.iter().enumerate().take(MAX_FIELDS) {
+        report.push_str(&format!(
+            "    field {}: {}..={} ({} lines)\n",
+            idx + 1,
+            range.min,
+            range.max,
+            range.count
+        ));
+    }
+    if ranges.len() > MAX_FIELDS {
+        report.push_str(&format!(
+            "    ... {} more fields\n",
+            ranges.len() - MAX_FIELDS
+        ));
+    }
+}
+
+fn describe_group(report: &mut String, group: &Group) {
+    report.push_str(&format!(
+        "  lines {}-{}\n",
+        group.start,
+        group.start + group.lines.len() - 1
+    ));
+    describe_lines(report, &group.lines);
+    if let Some(grid) = detect_grid(&group.lines) {
+        report.push_str(&format!("    grid: {}\n", format_grid(&grid)));
+    }
+}
+
+// The first group is a header (e.g. the numbers drawn before the bingo
+// boards) when it is shorter, or has longer lines, than every other group
+fn is_header(groups: &[Group]) -> bool {
+    let max_len = |group: &Group| {
+        group
+            .lines
+            .iter()
+            .map(|x| x.chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+
+    match groups.split_first() {
+        Some((first, rest)) if !rest.is_empty() => {
+            rest.iter().all(|x| first.lines.len() < x.lines.len())
+                || rest.iter().all(|x| max_len(first) > max_len(x))
+        }
+        _ => false,
+    }
+}
+
+fn inspect(contents: &str) -> String {
+    let lines: Vec<&str> = contents.lines().collect();
+    let num_blank = lines.iter().filter(|x| x.trim().is_empty()).count();
+
+    let mut report = format!("lines: {} ({} blank)\n", lines.len(), num_blank);
+    if let Some((min, max, modal)) = length_stats(&lines) {
+        report.push_str(&format!(
+            "length: min {}, max {}, modal {}\n",
+            min, max, modal
+        ));
+    }
+    report.push_str(
+        "legend: 9 digit, a lower, A upper, _ space, . punctuation, * mixed\n",
+    );
+
+    let groups = split_groups(&lines);
+    if groups.len() <= MAX_SECTIONS {
+        report.push_str(&format!("groups: {}\n", groups.len()));
+        for group in &groups {
+            describe_group(&mut report, group);
+        }
+
+        return report;
+    }
+
+    let records = if is_header(&groups) {
+        report.push_str(&format!(
+            "groups: 1 header, {} records\n",
+            groups.len() - 1
+        ));
+        describe_group(&mut report, &groups[0]);
+        &groups[1..]
+    } else {
+        report.push_str(&format!("groups: {} records\n", groups.len()));
+        &groups[..]
+    };
+
+    let min = records.iter().map(|x| x.lines.len()).min().unwrap();
+    let max = records.iter().map(|x| x.lines.len()).max().unwrap();
+    report.push_str(&format!("  records of {}-{} lines\n", min, max));
+
+    let all_lines: Vec<&str> =
+        records.iter().flat_map(|x| x.lines.clone()).collect();
+    describe_lines(&mut report, &all_lines);
+
+    // Only report grids shared by most records, small groups of equal length
+    // lines are grids by accident
+    let mut grids: Vec<(Grid, usize)> = Vec::new();
+    for grid in records.iter().filter_map(|x| detect_grid(&x.lines)) {
+        match grids.iter_mut().find(|(x, _)| *x == grid) {
+            Some((_, count)) => *count += 1,
+            None => grids.push((grid, 1)),
+        }
+    }
+    for (grid, count) in grids {
+        if 2 * count > records.len() {
+            report.push_str(&format!(
+                "    grid: {} of {} records are {}\n",
+                count,
+                records.len(),
+                format_grid(&grid)
+            ));
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> Vec<&'static str> {
+        vec![
+            "    [D]    ",
+            "[N] [C]    ",
+            "[Z] [M] [P]",
+            " 1   2   3 ",
+            "",
+            "move 1 from 2 to 1",
+            "move 3 from 1 to 3",
+            "move 2 from 2 to 1",
+            "move 1 from 1 to 2",
+        ]
+    }
+
+    #[test]
+    fn test_split_groups() {
+        let lines = get_test_input();
+        let groups = split_groups(&lines);
+        assert_eq!(groups.len(), 2);
+        assert_eq!((groups[0].start, groups[0].lines.len()), (1, 4));
+        assert_eq!((groups[1].start, groups[1].lines.len()), (6, 4));
+    }
+
+    #[test]
+    fn test_length_stats() {
+        assert_eq!(length_stats(&get_test_input()), Some((0, 18, 11)));
+        assert_eq!(length_stats(&[]), None);
+    }
+
+    #[test]
+    fn test_column_profile() {
+        assert_eq!(column_profile(&get_test_input()[..3]), "***_.A._***");
+        assert_eq!(column_profile(&["ab12", "cd34"]), "aa99");
+    }
+
+    #[test]
+    fn test_numeric_fields() {
+        assert_eq!(numeric_fields("move 13 from 2 to 1"), vec![13, 2, 1]);
+        assert_eq!(numeric_fields("2-4,6-8"), vec![2, 4, 6, 8]);
+        assert_eq!(numeric_fields("x=-3, y=12"), vec![-3, 12]);
+        assert!(numeric_fields("[A] [B]").is_empty());
+    }
+
+    #[test]
+    fn test_field_ranges() {
+        let lines = get_test_input();
+        assert_eq!(
+            field_ranges(&lines[5..]),
+            vec![
+                FieldRange {
+                    min: 1,
+                    max: 3,
+                    count: 4
+                },
+                FieldRange {
+                    min: 1,
+                    max: 2,
+                    count: 4
+                },
+                FieldRange {
+                    min: 1,
+                    max: 3,
+                    count: 4
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_grid() {
+        assert_eq!(
+            detect_grid(&["#..#", "..#.", "#..."]),
+            Some(Grid::Chars { rows: 3, cols: 4 })
+        );
+        assert_eq!(
+            detect_grid(&["22 13 17", " 8  2 23"]),
+            Some(Grid::Numbers { rows: 2, cols: 3 })
+        );
+        assert_eq!(detect_grid(&get_test_input()[5..]), None);
+        assert_eq!(detect_grid(&["#..#"]), None);
+    }
+
+    #[test]
+    fn test_inspect() {
+        let report = inspect(&get_test_input().join("\n"));
+        assert!(report.starts_with("lines: 9 (1 blank)\n"));
+        assert!(report.contains("groups: 2\n  lines 1-4\n"));
+        assert!(
+            report.contains("  lines 6-9\n    columns: aaaa_9_aaaa_9_aa_9\n")
+        );
+    }
+
+    #[test]
+    fn test_inspect_records() {
+        let board = "22 13 17\n 8  2 23\n21  9 14";
+        let mut groups = vec!["7,4,9,5,11,17,23,2,0,14,21,24", "1 2 3"];
+        groups.extend([board; 5]);
+
+        let report = inspect(&groups.join("\n\n"));
+        assert!(report.contains("groups: 1 header, 6 records\n  lines 1-1\n"));
+        assert!(report.contains("    ... 2 more fields\n"));
+        assert!(report.contains("  records of 1-3 lines\n"));
+        assert!(report.contains("    grid: 5 of 6 records are 3x3 numbers\n"));
+    }
+}
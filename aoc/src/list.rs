@@ -0,0 +1,75 @@
+use super::args::Args;
+use super::YEARS;
+use common::solution::{Solution, Tag};
+use std::str::FromStr;
+
+pub fn main(args: &Args) -> Result<(), String> {
+    let year: Option<u32> = args.parse("year")?;
+    let tag = match args.get("tag") {
+        Some(tag) => Some(
+            Tag::from_str(tag).map_err(|_| format!("Unknown tag {}", tag))?,
+        ),
+        None => None,
+    };
+
+    let solutions = YEARS.iter().flat_map(|(_, solutions)| solutions.iter());
+    for solution in filter_solutions(solutions, year, tag) {
+        println!("{}", format_solution(solution));
+    }
+
+    Ok(())
+}
+
+fn filter_solutions<'a>(
+    solutions: impl Iterator<Item = &'a Solution>,
+    year: Option<u32>,
+    tag: Option<Tag>,
+) -> Vec<&'a Solution> {
+    solutions
+        .filter(|x| year.map_or(true, |year| x.year == year))
+        .filter(|x| tag.map_or(true, |tag| x.tags.contains(&tag)))
+        .collect()
+}
+
+fn format_solution(solution: &Solution) -> String {
+    let tags: Vec<String> =
+        solution.tags.iter().map(|x| x.to_string()).collect();
+    format!(
+        "{} day{:02} {:<2} {:<28} [{}] {}",
+        solution.year,
+        solution.day,
+        "*".repeat(solution.stars()),
+        solution.title,
+        tags.join(", "),
+        solution.url()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_filter_solutions() {
+        let solutions =
+            YEARS.iter().flat_map(|(_, solutions)| solutions.iter());
+        let found = filter_solutions(solutions, None, Some(Tag::Intervals));
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].year, found[0].day), (2022, 4));
+
+        let solutions =
+            YEARS.iter().flat_map(|(_, solutions)| solutions.iter());
+        let found = filter_solutions(solutions, Some(2021), None);
+        assert!(found.iter().all(|x| x.year == 2021));
+    }
+
+    #[test]
+    fn test_format_solution() {
+        let solution = &year2022::SOLUTIONS[3];
+        assert_eq!(
+            format_solution(solution),
+            "2022 day04 ** Camp Cleanup                 [parsing, intervals] \
+             https://adventofcode.com/2022/day/4"
+        );
+    }
+}
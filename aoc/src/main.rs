@@ -0,0 +1,86 @@
+// Filters read as "no value matches everything", e.g. year.map_or(true, ..)
+#![allow(clippy::unnecessary_map_or)]
+
+mod alloc;
+mod args;
+mod bench;
+mod calendar;
+mod diagnostic;
+mod dive;
+mod inspect;
+mod list;
+mod report;
+mod watch;
+
+use alloc::CountingAllocator;
+use args::Args;
+use common::solution::Solution;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const USAGE: &str = "Usage:
+    aoc watch --year <year> --day <day>
+    aoc calendar [--year <year>]
+    aoc list [--year <year>] [--tag <tag>]
+    aoc bench --year <year> [--day <day>]
+    aoc report --year <year> [--redact]
+    aoc inspect <file>
+    aoc diagnostic [--ties <policy>] <file>
+    aoc dive --model <model>[,<model>...] [--csv <file>] [--svg <file>] <file>";
+
+type Main = fn(&Args) -> Result<(), String>;
+
+pub const YEARS: [(u32, &[Solution]); 2] =
+    [(2021, year2021::SOLUTIONS), (2022, year2022::SOLUTIONS)];
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.split_first() {
+        Some((command, args)) => {
+            // Each subcommand with the number of positional arguments it takes
+            let (main, num_positional): (Main, usize) = match command.as_str() {
+                "watch" => (watch::main, 0),
+                "calendar" => (calendar::main, 0),
+                "list" => (list::main, 0),
+                "bench" => (bench::main, 0),
+                "report" => (report::main, 0),
+                "inspect" => (inspect::main, 1),
+                "dive" => (dive::main, 1),
+                "diagnostic" => (diagnostic::main, 1),
+                _ => (|_: &Args| Err(USAGE.to_string()), 0),
+            };
+            Args::new(args, num_positional).and_then(|args| main(&args))
+        }
+        None => Err(USAGE.to_string()),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+pub fn year_solutions(year: u32) -> Result<&'static [Solution], String> {
+    YEARS
+        .iter()
+        .find(|(curr_year, _)| *curr_year == year)
+        .map(|(_, solutions)| *solutions)
+        .ok_or(format!("No solutions for year {}", year))
+}
+
+pub fn repo_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+pub fn year_dir(year: u32) -> Result<PathBuf, String> {
+    let year_dir = repo_root().join(format!("year{}", year));
+    if year_dir.is_dir() {
+        Ok(year_dir)
+    } else {
+        Err(format!("No solutions for year {}", year))
+    }
+}
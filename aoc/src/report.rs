@@ -0,0 +1,212 @@
+use super::args::Args;
+use super::bench::{format_duration, history_path, read_history, BenchRecord};
+use super::{year_dir, year_solutions};
+use common::solution::Solution;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct ReportRow<'a> {
+    solution: &'a Solution,
+    // Most recent benchmark of the solution, if it has ever been benchmarked
+    record: Option<&'a BenchRecord>,
+    lines: Option<usize>,
+}
+
+pub fn main(args: &Args) -> Result<(), String> {
+    let year: u32 = args.require("year")?;
+    let redact = args.has_flag("redact");
+
+    let solutions = year_solutions(year)?;
+    let history = read_history(&history_path())?;
+    let year_dir = year_dir(year)?;
+
+    let rows: Vec<ReportRow> = solutions
+        .iter()
+        .map(|solution| {
+            let files = source_files(&year_dir, solution);
+            ReportRow {
+                solution,
+                record: history.iter().rev().find(|record| {
+                    record.year == solution.year && record.day == solution.day
+                }),
+                lines: count_source_lines(&files),
+            }
+        })
+        .collect();
+
+    print!("{}", render_report(year, &rows, redact));
+
+    Ok(())
+}
+
+fn source_path(solution: &Solution) -> String {
+    format!("src/day{:02}.rs", solution.day)
+}
+
+// The day's module and, if it's split up, every file under src/dayNN/
+fn source_files(year_dir: &Path, solution: &Solution) -> Vec<PathBuf> {
+    let mut files = vec![year_dir.join(source_path(solution))];
+    let mut dirs = vec![year_dir.join(format!("src/day{:02}", solution.day))];
+
+    while let Some(dir) = dirs.pop() {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.extension().is_some_and(|x| x == "rs") {
+                    files.push(path);
+                }
+            }
+        }
+    }
+
+    files[1..].sort();
+    files
+}
+
+// None if none of the files can be read
+fn count_source_lines(files: &[PathBuf]) -> Option<usize> {
+    let counts: Vec<usize> = files
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|source| count_lines_of_code(&source))
+        .collect();
+    if counts.is_empty() {
+        None
+    } else {
+        Some(counts.iter().sum())
+    }
+}
+
+// Non-empty, non-comment lines before the test module
+fn count_lines_of_code(source: &str) -> usize {
+    source
+        .lines()
+        .take_while(|line| !line.starts_with("#[cfg(test)]"))
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .count()
+}
+
+fn render_report(year: u32, rows: &[ReportRow], redact: bool) -> String {
+    let stars: usize = rows.iter().map(|row| row.solution.stars()).sum();
+    let mut report = format!(
+        "# Advent of Code {}\n\n{}/50 stars\n\n\
+         | Day | Title | Tags | Part 1 | Part 2 | Time | Allocations \
+         | Lines | Source |\n\
+         | --- | --- | --- | --- | --- | --- | --- | --- | --- |\n",
+        year, stars
+    );
+
+    for row in rows {
+        let solution = row.solution;
+        let answers: Vec<String> = solution
+            .answers
+            .iter()
+            .map(|answer| match answer {
+                Some(_) if redact => "redacted".to_string(),
+                Some(answer) => format!("`{}`", answer),
+                None => "-".to_string(),
+            })
+            .collect();
+        let tags: Vec<String> =
+            solution.tags.iter().map(|x| x.to_string()).collect();
+        let source = source_path(solution);
+
+        report.push_str(&format!(
+            "| {} | [{}]({}) | {} | {} | {} | {} | {} | {} | [{}](year{}/{}) |\n",
+            solution.day,
+            solution.title,
+            solution.url(),
+            tags.join(", "),
+            answers[0],
+            answers[1],
+            row.record.map_or("-".to_string(), |x| format_duration(x.time)),
+            row.record.map_or("-".to_string(), |x| x.allocations.to_string()),
+            row.lines.map_or("-".to_string(), |x| x.to_string()),
+            source.trim_start_matches("src/"),
+            year,
+            source
+        ));
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_count_lines_of_code() {
+        let source = "use std::fs;\n\n// Comment\nfn main() {\n    \
+                      // Comment\n    let x = 1;\n}\n\n#[cfg(test)]\n\
+                      mod test {}\n";
+        assert_eq!(count_lines_of_code(source), 4);
+    }
+
+    #[test]
+    fn test_source_files() {
+        let year_dir = super::super::year_dir(2021).unwrap();
+        let day02 = &year2021::SOLUTIONS[1];
+        let files = source_files(&year_dir, day02);
+        assert_eq!(
+            files,
+            vec![
+                year_dir.join("src/day02.rs"),
+                year_dir.join("src/day02/planner.rs"),
+                year_dir.join("src/day02/script.rs"),
+                year_dir.join("src/day02/trajectory.rs"),
+            ]
+        );
+
+        let module = fs::read_to_string(&files[0]).unwrap();
+        let lines = count_source_lines(&files).unwrap();
+        assert!(lines > count_lines_of_code(&module));
+
+        let day01 = &year2021::SOLUTIONS[0];
+        assert_eq!(source_files(&year_dir, day01).len(), 1);
+        assert_eq!(count_source_lines(&[year_dir.join("src/day00.rs")]), None);
+    }
+
+    #[test]
+    fn test_render_report() {
+        let record = BenchRecord {
+            timestamp: 1671000000,
+            year: 2022,
+            day: 1,
+            time: Duration::from_micros(1500),
+            allocations: 2300,
+        };
+        let rows = vec![
+            ReportRow {
+                solution: &year2022::SOLUTIONS[0],
+                record: Some(&record),
+                lines: Some(40),
+            },
+            ReportRow {
+                solution: &year2022::SOLUTIONS[1],
+                record: None,
+                lines: None,
+            },
+        ];
+
+        let report = render_report(2022, &rows, false);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "# Advent of Code 2022");
+        assert_eq!(lines[2], "4/50 stars");
+        assert_eq!(
+            lines[6],
+            "| 1 | [Calorie Counting](https://adventofcode.com/2022/day/1) \
+             | parsing, sorting | `73211` | `213958` | 1.5 ms | 2300 | 40 \
+             | [day01.rs](year2022/src/day01.rs) |"
+        );
+        assert!(lines[7].contains("| `13268` | `15508` | - | - | - |"));
+
+        let report = render_report(2022, &rows, true);
+        assert!(!report.contains("73211"));
+        assert!(report.contains("| redacted | redacted |"));
+    }
+}
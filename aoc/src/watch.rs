@@ -0,0 +1,331 @@
+use super::args::Args;
+use super::{year_dir, year_solutions};
+use common::solution::Solution;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn main(args: &Args) -> Result<(), String> {
+    let year: u32 = args.require("year")?;
+    let day: u32 = args.require("day")?;
+    let year_dir = year_dir(year)?;
+    let solution = find_solution(year, day)?;
+
+    println!(
+        "Watching year{}/src/day{:02}.rs and year{}/data/day{:02}*",
+        year, day, year, day
+    );
+
+    let mut snapshot = HashMap::new();
+    let mut prev_answers = Vec::new();
+    loop {
+        let curr_snapshot = take_snapshot(&watched_files(&year_dir, day));
+        if curr_snapshot != snapshot {
+            let changed = changed_files(&snapshot, &curr_snapshot);
+            let changed: Vec<String> = changed
+                .iter()
+                .map(|path| {
+                    let path = path.strip_prefix(&year_dir).unwrap_or(path);
+                    path.display().to_string()
+                })
+                .collect();
+            println!("== {} day{:02}: {}", year, day, changed.join(", "));
+
+            prev_answers = run_day(&year_dir, solution, &prev_answers)?;
+            snapshot = curr_snapshot;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+// Only registered days can be run, so don't wait on one that isn't
+fn find_solution(year: u32, day: u32) -> Result<&'static Solution, String> {
+    year_solutions(year)?
+        .iter()
+        .find(|solution| solution.day == day)
+        .ok_or(format!("No solution for {} day{:02}", year, day))
+}
+
+// The day's module, its input and any example fixtures next to the input,
+// e.g. "data/day06_example.txt"
+fn watched_files(year_dir: &Path, day: u32) -> Vec<PathBuf> {
+    let prefix = format!("day{:02}", day);
+    let mut files = vec![year_dir.join("src").join(format!("{}.rs", prefix))];
+
+    if let Ok(entries) = fs::read_dir(year_dir.join("data")) {
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                files.push(entry.path());
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+fn take_snapshot(files: &[PathBuf]) -> HashMap<PathBuf, SystemTime> {
+    files
+        .iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(path).and_then(|x| x.modified());
+            modified.ok().map(|modified| (path.clone(), modified))
+        })
+        .collect()
+}
+
+fn changed_files(
+    prev: &HashMap<PathBuf, SystemTime>,
+    curr: &HashMap<PathBuf, SystemTime>,
+) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = prev
+        .keys()
+        .chain(curr.keys())
+        .filter(|path| prev.get(*path) != curr.get(*path))
+        .cloned()
+        .collect();
+    changed.sort();
+    changed.dedup();
+    changed
+}
+
+fn cargo(year_dir: &Path, args: &[&str]) -> Result<Output, String> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    Command::new(cargo)
+        .args(args)
+        .current_dir(year_dir)
+        .output()
+        .map_err(|err| format!("Could not run cargo: {}", err))
+}
+
+// Run the day's tests and then its parts, returning the answers it printed
+fn run_day(
+    year_dir: &Path,
+    solution: &Solution,
+    prev_answers: &[String],
+) -> Result<Vec<String>, String> {
+    let package = format!("year{}", solution.year);
+    let filter = format!("day{:02}::", solution.day);
+
+    let output = cargo(year_dir, &["test", "-p", &package, "--lib", &filter])?;
+    match parse_test_output(&String::from_utf8_lossy(&output.stdout)) {
+        Some(summary) => {
+            println!(
+                "tests: {} passed, {} failed",
+                summary.passed,
+                summary.failed.len()
+            );
+            for name in summary.failed {
+                println!("  FAILED {}", name);
+            }
+        }
+        None => {
+            println!("build: FAILED");
+            print_errors(&output);
+            return Ok(prev_answers.to_vec());
+        }
+    }
+
+    let day = solution.day.to_string();
+    let output = cargo(year_dir, &["run", "-q", "-p", &package, "--", &day])?;
+    let mut answers: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect();
+
+    if output.status.success() {
+        println!("run: ok");
+        if answers.is_empty() {
+            answers = registry_answers(solution);
+        }
+    } else {
+        println!("run: FAILED");
+        print_errors(&output);
+    }
+
+    for line in diff_answers(prev_answers, &answers) {
+        println!("  {}", line);
+    }
+
+    Ok(answers)
+}
+
+// Some solutions assert their accepted answers rather than print them, so a
+// run that succeeds without printing any produced those
+fn registry_answers(solution: &Solution) -> Vec<String> {
+    solution
+        .answers
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, answer)| {
+            let answer = (*answer)?;
+            Some(format!(
+                "day{:02} part{}: {}",
+                solution.day,
+                idx + 1,
+                answer
+            ))
+        })
+        .collect()
+}
+
+fn print_errors(output: &Output) {
+    String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with("note:"))
+        .for_each(|line| println!("  {}", line));
+}
+
+#[derive(Debug, PartialEq)]
+struct TestSummary {
+    passed: usize,
+    failed: Vec<String>,
+}
+
+// None means no test binary ran, i.e. the build failed
+fn parse_test_output(stdout: &str) -> Option<TestSummary> {
+    let mut summary = TestSummary {
+        passed: 0,
+        failed: Vec::new(),
+    };
+    let mut found_result = false;
+
+    for line in stdout.lines() {
+        if line.starts_with("test result:") {
+            found_result = true;
+        } else if let Some(test) = line.strip_prefix("test ") {
+            match test.rsplit_once(" ... ") {
+                Some((_, "ok")) => summary.passed += 1,
+                Some((name, "FAILED")) => summary.failed.push(name.to_string()),
+                _ => (),
+            }
+        }
+    }
+
+    if found_result {
+        Some(summary)
+    } else {
+        None
+    }
+}
+
+// Compare answers of the form "label: value" with those of the previous run
+fn diff_answers(prev: &[String], curr: &[String]) -> Vec<String> {
+    let prev: HashMap<&str, &str> = prev
+        .iter()
+        .filter_map(|line| line.split_once(": "))
+        .collect();
+
+    curr.iter()
+        .map(|line| match line.split_once(": ") {
+            Some((label, value)) => match prev.get(label) {
+                Some(&prev_value) if prev_value != value => {
+                    format!("{} (was {})", line, prev_value)
+                }
+                Some(_) => line.clone(),
+                None if prev.is_empty() => line.clone(),
+                None => format!("{} (new)", line),
+            },
+            None => line.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_vec(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn test_watched_files() {
+        let year_dir = year_dir(2022).unwrap();
+        assert_eq!(
+            watched_files(&year_dir, 5),
+            vec![
+                year_dir.join("data/day05.txt"),
+                year_dir.join("src/day05.rs")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_solution() {
+        assert_eq!(find_solution(2022, 5).unwrap().title, "Supply Stacks");
+        assert_eq!(
+            find_solution(2022, 6).err(),
+            Some("No solution for 2022 day06".to_string())
+        );
+        assert!(find_solution(2019, 1).is_err());
+    }
+
+    #[test]
+    fn test_registry_answers() {
+        let solution = find_solution(2022, 5).unwrap();
+        assert_eq!(
+            registry_answers(solution),
+            to_vec(&["day05 part1: CNSZFDVLJ", "day05 part2: QNDWLMGNS"])
+        );
+    }
+
+    #[test]
+    fn test_changed_files() {
+        let now = SystemTime::now();
+        let later = now + Duration::from_secs(1);
+        let prev = HashMap::from([
+            (PathBuf::from("a"), now),
+            (PathBuf::from("b"), now),
+        ]);
+        let curr = HashMap::from([
+            (PathBuf::from("b"), later),
+            (PathBuf::from("c"), now),
+        ]);
+        assert_eq!(
+            changed_files(&prev, &curr),
+            vec![PathBuf::from("a"), PathBuf::from("b"), PathBuf::from("c")]
+        );
+        assert!(changed_files(&curr, &curr).is_empty());
+    }
+
+    #[test]
+    fn test_parse_test_output() {
+        let stdout = "\nrunning 3 tests\n\
+                      test day05::test::test_a ... ok\n\
+                      test day05::test::test_b ... FAILED\n\
+                      test day05::test::test_c ... ok\n\n\
+                      test result: FAILED. 2 passed; 1 failed; 0 ignored\n";
+        assert_eq!(
+            parse_test_output(stdout),
+            Some(TestSummary {
+                passed: 2,
+                failed: vec!["day05::test::test_b".to_string()],
+            })
+        );
+        assert_eq!(parse_test_output(""), None);
+    }
+
+    #[test]
+    fn test_diff_answers() {
+        let prev = to_vec(&["day01 part1: 7", "day01 part2: 5"]);
+        let curr = to_vec(&["day01 part1: 7", "day01 part2: 6", "x: 1", "y"]);
+        assert_eq!(
+            diff_answers(&prev, &curr),
+            to_vec(&[
+                "day01 part1: 7",
+                "day01 part2: 6 (was 5)",
+                "x: 1 (new)",
+                "y"
+            ])
+        );
+        assert_eq!(diff_answers(&[], &prev), prev);
+    }
+}
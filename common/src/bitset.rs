@@ -0,0 +1,194 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, Sub};
+
+// Fixed-size set of small integers, 64 per word. The default single word is
+// enough for e.g. rucksack item priorities 1-52.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const WORDS: usize = 1> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    pub const CAPACITY: usize = WORDS * 64;
+
+    pub fn new() -> Self {
+        BitSet { words: [0; WORDS] }
+    }
+
+    // Returns whether the value was newly inserted, panics if the value is
+    // not below CAPACITY
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = Self::locate(value);
+        let is_new = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        is_new
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, bit) = Self::locate(value);
+        let was_present = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        was_present
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && {
+            let (word, bit) = Self::locate(value);
+            self.words[word] & bit != 0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|x| x.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|x| *x == 0)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    // Values in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        let words = self.words;
+        (0..WORDS).flat_map(move |idx| {
+            let mut word = words[idx];
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(idx * 64 + bit)
+            })
+        })
+    }
+
+    fn locate(value: usize) -> (usize, u64) {
+        assert!(
+            value < Self::CAPACITY,
+            "{} out of range for BitSet of capacity {}",
+            value,
+            Self::CAPACITY
+        );
+        (value / 64, 1 << (value % 64))
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let mut words = self.words;
+        for (word, other) in words.iter_mut().zip(other.words.iter()) {
+            *word = f(*word, *other);
+        }
+        BitSet { words }
+    }
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        BitSet::new()
+    }
+}
+
+impl<const WORDS: usize> fmt::Debug for BitSet<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl<const WORDS: usize> BitAnd for BitSet<WORDS> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(&other)
+    }
+}
+
+impl<const WORDS: usize> BitOr for BitSet<WORDS> {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(&other)
+    }
+}
+
+impl<const WORDS: usize> Sub for BitSet<WORDS> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.difference(&other)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> (BitSet, BitSet) {
+        let a: BitSet = [1, 5, 52, 63].iter().copied().collect();
+        let b: BitSet = [5, 7, 63].iter().copied().collect();
+        (a, b)
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set: BitSet = BitSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.contains(3) && !set.contains(4) && !set.contains(64));
+        assert_eq!(set.len(), 1);
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_out_of_range() {
+        BitSet::<1>::new().insert(64);
+    }
+
+    #[test]
+    fn test_operations() {
+        let (a, b) = get_test_input();
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![5, 63]);
+        assert_eq!((a | b).iter().collect::<Vec<_>>(), vec![1, 5, 7, 52, 63]);
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![1, 52]);
+        assert_eq!((a | b).len(), 5);
+        assert!((a & b).is_subset(&a) && !a.is_subset(&b));
+    }
+
+    #[test]
+    fn test_multiple_words() {
+        let set: BitSet<3> = [0, 64, 100, 191].iter().copied().collect();
+        assert_eq!(BitSet::<3>::CAPACITY, 192);
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 64, 100, 191]);
+        assert_eq!(format!("{:?}", set), "{0, 64, 100, 191}");
+    }
+}
@@ -0,0 +1,327 @@
+use crate::grid::Grid;
+use std::cmp::Ordering;
+
+// What to do when several symbols are equally common in a column
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ties {
+    // Most common goes to the tied symbol earliest in the alphabet, least
+    // common to the one latest in it
+    Prefer,
+    Error,
+    // Leave the column out of the consensus, or don't filter on it
+    Skip,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Keep {
+    MostCommon,
+    LeastCommon,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConsensusError {
+    Tie { column: usize },
+    UnknownSymbol { row: usize, column: usize },
+    Empty,
+}
+
+// Majority votes down the columns of a grid of symbols, one reading per row.
+// The alphabet lists every symbol a reading can hold, so a symbol that never
+// shows up in a column is still its least common one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Consensus<T> {
+    alphabet: Vec<T>,
+    ties: Ties,
+    // Alphabet indexes sorted by symbol, for lookups and for walking readings
+    // in sorted order
+    by_symbol: Vec<usize>,
+}
+
+impl<T: Ord> Consensus<T> {
+    pub fn new(alphabet: Vec<T>, ties: Ties) -> Self {
+        assert!(!alphabet.is_empty(), "Alphabet must not be empty");
+        let mut by_symbol: Vec<usize> = (0..alphabet.len()).collect();
+        by_symbol.sort_by(|a, b| alphabet[*a].cmp(&alphabet[*b]));
+        Consensus {
+            alphabet,
+            ties,
+            by_symbol,
+        }
+    }
+
+    // The symbols in the grid, in sorted order
+    pub fn from_grid(grid: &Grid<T>, ties: Ties) -> Self
+    where
+        T: Clone,
+    {
+        let mut alphabet: Vec<T> =
+            grid.iter().map(|(_, x)| x.clone()).collect();
+        alphabet.sort();
+        alphabet.dedup();
+        Consensus::new(alphabet, ties)
+    }
+
+    pub fn alphabet(&self) -> &[T] {
+        &self.alphabet
+    }
+
+    // Count of each symbol in each column, in alphabet order
+    pub fn frequencies(
+        &self,
+        grid: &Grid<T>,
+    ) -> Result<Vec<Vec<usize>>, ConsensusError> {
+        let mut counts = vec![vec![0; self.alphabet.len()]; grid.num_cols()];
+        for ((row, column), symbol) in grid.iter() {
+            let idx = self
+                .index(symbol)
+                .ok_or(ConsensusError::UnknownSymbol { row, column })?;
+            counts[column][idx] += 1;
+        }
+
+        Ok(counts)
+    }
+
+    // Most common symbol of each column, e.g. the gamma rate for bits
+    pub fn consensus(&self, grid: &Grid<T>) -> Result<Vec<T>, ConsensusError>
+    where
+        T: Clone,
+    {
+        self.select(&self.frequencies(grid)?, Keep::MostCommon)
+    }
+
+    // Least common symbol of each column, e.g. the epsilon rate for bits
+    pub fn least_common(&self, grid: &Grid<T>) -> Result<Vec<T>, ConsensusError>
+    where
+        T: Clone,
+    {
+        self.select(&self.frequencies(grid)?, Keep::LeastCommon)
+    }
+
+    // As consensus and least_common, from frequency tables in alphabet order
+    // counted some faster way, e.g. with popcounts for bits
+    pub fn select(
+        &self,
+        frequencies: &[Vec<usize>],
+        keep: Keep,
+    ) -> Result<Vec<T>, ConsensusError>
+    where
+        T: Clone,
+    {
+        let mut symbols = Vec::new();
+        for (column, counts) in frequencies.iter().enumerate() {
+            if let Some(idx) = self.pick(counts, column, keep, false)? {
+                symbols.push(self.alphabet[idx].clone());
+            }
+        }
+
+        Ok(symbols)
+    }
+
+    // Row of the reading left after keeping, column by column, the readings
+    // with the most (or least) common symbol among those still kept, until
+    // one is left. Only symbols held by a kept reading count, so readings
+    // that all agree on a column stay. If several readings are left at the
+    // end the smallest, earliest one is taken.
+    //
+    // The readings are sorted once, so the ones kept are always ranges of
+    // them sharing their first symbols, each split at every column into runs
+    // of one symbol. Skipped columns keep every run.
+    pub fn filter(
+        &self,
+        grid: &Grid<T>,
+        keep: Keep,
+    ) -> Result<usize, ConsensusError> {
+        // Checks every symbol is in the alphabet, so each run is one symbol
+        self.frequencies(grid)?;
+        if grid.num_rows() == 0 {
+            return Err(ConsensusError::Empty);
+        }
+
+        let mut sorted: Vec<usize> = (0..grid.num_rows()).collect();
+        sorted.sort_by(|a, b| grid.row(*a).iter().cmp(grid.row(*b)));
+
+        let mut ranges: Vec<&[usize]> = vec![&sorted];
+        for column in 0..grid.num_cols() {
+            if ranges.iter().map(|x| x.len()).sum::<usize>() == 1 {
+                break;
+            }
+
+            let mut runs: Vec<Vec<&[usize]>> =
+                vec![Vec::new(); self.alphabet.len()];
+            let mut all = Vec::new();
+            for range in &ranges {
+                let mut rest = *range;
+                for idx in &self.by_symbol {
+                    let symbol = &self.alphabet[*idx];
+                    let (run, after) =
+                        rest.split_at(rest.partition_point(|row| {
+                            grid[(*row, column)] <= *symbol
+                        }));
+                    if !run.is_empty() {
+                        runs[*idx].push(run);
+                        all.push(run);
+                    }
+                    rest = after;
+                }
+            }
+
+            let counts: Vec<usize> = runs
+                .iter()
+                .map(|x| x.iter().map(|run| run.len()).sum())
+                .collect();
+            ranges = match self.pick(&counts, column, keep, true)? {
+                Some(idx) => runs.swap_remove(idx),
+                None => all,
+            };
+        }
+
+        Ok(ranges[0][0])
+    }
+
+    // Index of the chosen symbol, None to skip the column. Symbols with a
+    // count of zero are only candidates if not present_only.
+    fn pick(
+        &self,
+        counts: &[usize],
+        column: usize,
+        keep: Keep,
+        present_only: bool,
+    ) -> Result<Option<usize>, ConsensusError> {
+        let better = |a: usize, b: usize| match keep {
+            Keep::MostCommon => a.cmp(&b),
+            Keep::LeastCommon => b.cmp(&a),
+        };
+
+        let mut tied: Vec<usize> = Vec::new();
+        for (idx, count) in counts.iter().enumerate() {
+            if present_only && *count == 0 {
+                continue;
+            }
+            match tied.first().map(|x| better(*count, counts[*x])) {
+                Some(Ordering::Less) => (),
+                Some(Ordering::Equal) => tied.push(idx),
+                _ => tied = vec![idx],
+            }
+        }
+
+        match (tied.as_slice(), self.ties) {
+            ([idx], _) => Ok(Some(*idx)),
+            ([], _) => Ok(None),
+            (_, Ties::Prefer) => Ok(match keep {
+                Keep::MostCommon => tied.first().copied(),
+                Keep::LeastCommon => tied.last().copied(),
+            }),
+            (_, Ties::Error) => Err(ConsensusError::Tie { column }),
+            (_, Ties::Skip) => Ok(None),
+        }
+    }
+
+    fn index(&self, symbol: &T) -> Option<usize> {
+        self.by_symbol
+            .binary_search_by(|idx| self.alphabet[*idx].cmp(symbol))
+            .ok()
+            .map(|x| self.by_symbol[x])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> Grid<char> {
+        Grid::parse_chars(
+            "eedadn\ndrvtee\neandsr\nraavrd\natevrs\ntsrnev\nsdttsa\n\
+             rasrtv\nnssdts\nntnada\nsvetve\ntesnvt\nvntsnd\nvrdear\n\
+             dvrsen\nenarar",
+        )
+        .unwrap()
+    }
+
+    fn to_string(symbols: Vec<char>) -> String {
+        symbols.into_iter().collect()
+    }
+
+    #[test]
+    fn test_consensus() {
+        let grid = get_test_input();
+        let consensus = Consensus::from_grid(&grid, Ties::Error);
+        assert_eq!(
+            consensus.alphabet(),
+            ['a', 'd', 'e', 'n', 'r', 's', 't', 'v']
+        );
+        assert_eq!(to_string(consensus.consensus(&grid).unwrap()), "easter");
+
+        // Every letter shows up in every column, so the least common are too
+        let frequencies = consensus.frequencies(&grid).unwrap();
+        assert_eq!(frequencies[0], vec![1, 2, 3, 2, 2, 2, 2, 2]);
+        assert_eq!(to_string(consensus.least_common(&grid).unwrap()), "advent");
+        let selected = consensus.select(&frequencies, Keep::MostCommon);
+        assert_eq!(to_string(selected.unwrap()), "easter");
+    }
+
+    #[test]
+    fn test_ties() {
+        // The first column is tied between a and b, c never shows up in it
+        let grid = Grid::parse_chars("ab\nbb\nba\nab").unwrap();
+        let consensus = |ties| Consensus::new(vec!['b', 'a', 'c'], ties);
+
+        let prefer = consensus(Ties::Prefer);
+        assert_eq!(to_string(prefer.consensus(&grid).unwrap()), "bb");
+        assert_eq!(to_string(prefer.least_common(&grid).unwrap()), "cc");
+        let skip = consensus(Ties::Skip);
+        assert_eq!(to_string(skip.consensus(&grid).unwrap()), "b");
+        assert_eq!(
+            consensus(Ties::Error).consensus(&grid),
+            Err(ConsensusError::Tie { column: 0 })
+        );
+
+        // Filtering only counts the symbols left, so after keeping the two
+        // ab readings for the tie, their b is both most and least common
+        assert_eq!(prefer.filter(&grid, Keep::MostCommon), Ok(1));
+        assert_eq!(prefer.filter(&grid, Keep::LeastCommon), Ok(0));
+        // Skipping the tie keeps every reading, then those ending in b
+        assert_eq!(skip.filter(&grid, Keep::MostCommon), Ok(0));
+        assert_eq!(
+            consensus(Ties::Error).filter(&grid, Keep::MostCommon),
+            Err(ConsensusError::Tie { column: 0 })
+        );
+    }
+
+    #[test]
+    fn test_filter() {
+        let grid = get_test_input();
+        let consensus = Consensus::from_grid(&grid, Ties::Prefer);
+
+        // e is most common in the first column, then a, e and n tie
+        assert_eq!(consensus.filter(&grid, Keep::MostCommon), Ok(2));
+        // Only one reading starts with the least common a
+        assert_eq!(consensus.filter(&grid, Keep::LeastCommon), Ok(4));
+
+        // Duplicates agree on every column and are all kept to the end
+        let grid = Grid::parse_chars("xy\nxy\nxz").unwrap();
+        let consensus = Consensus::from_grid(&grid, Ties::Error);
+        assert_eq!(consensus.filter(&grid, Keep::MostCommon), Ok(0));
+        assert_eq!(consensus.filter(&grid, Keep::LeastCommon), Ok(2));
+    }
+
+    #[test]
+    fn test_errors() {
+        let grid = Grid::parse_chars("ab\nbx").unwrap();
+        let consensus = Consensus::new(vec!['a', 'b'], Ties::Prefer);
+        assert_eq!(
+            consensus.frequencies(&grid),
+            Err(ConsensusError::UnknownSymbol { row: 1, column: 1 })
+        );
+        assert_eq!(
+            consensus.filter(&grid, Keep::MostCommon),
+            Err(ConsensusError::UnknownSymbol { row: 1, column: 1 })
+        );
+
+        let grid = Grid::new(0, 2, 'a');
+        assert_eq!(consensus.consensus(&grid), Ok(vec!['a', 'a']));
+        assert_eq!(
+            consensus.filter(&grid, Keep::MostCommon),
+            Err(ConsensusError::Empty)
+        );
+    }
+}
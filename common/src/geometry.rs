@@ -0,0 +1,424 @@
+use num_traits::{PrimInt, Signed};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// Point + Vec, Point - Vec, Point - Point, Vec + Vec, Vec - Vec, Vec * scalar
+// and -Vec, component-wise
+macro_rules! impl_ops {
+    ($point:ident, $vec:ident, $($c:ident),+) => {
+        impl<T> $point<T> {
+            pub fn new($($c: T),+) -> Self {
+                $point { $($c),+ }
+            }
+        }
+
+        impl<T> $vec<T> {
+            pub fn new($($c: T),+) -> Self {
+                $vec { $($c),+ }
+            }
+        }
+
+        impl<T: Add<Output = T>> Add<$vec<T>> for $point<T> {
+            type Output = $point<T>;
+            fn add(self, other: $vec<T>) -> $point<T> {
+                $point { $($c: self.$c + other.$c),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub<$vec<T>> for $point<T> {
+            type Output = $point<T>;
+            fn sub(self, other: $vec<T>) -> $point<T> {
+                $point { $($c: self.$c - other.$c),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = $vec<T>;
+            fn sub(self, other: $point<T>) -> $vec<T> {
+                $vec { $($c: self.$c - other.$c),+ }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign<$vec<T>> for $point<T> {
+            fn add_assign(&mut self, other: $vec<T>) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign<$vec<T>> for $point<T> {
+            fn sub_assign(&mut self, other: $vec<T>) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $vec<T> {
+            type Output = $vec<T>;
+            fn add(self, other: $vec<T>) -> $vec<T> {
+                $vec { $($c: self.$c + other.$c),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $vec<T> {
+            type Output = $vec<T>;
+            fn sub(self, other: $vec<T>) -> $vec<T> {
+                $vec { $($c: self.$c - other.$c),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $vec<T> {
+            type Output = $vec<T>;
+            fn mul(self, scale: T) -> $vec<T> {
+                $vec { $($c: self.$c * scale),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $vec<T> {
+            type Output = $vec<T>;
+            fn neg(self) -> $vec<T> {
+                $vec { $($c: -self.$c),+ }
+            }
+        }
+
+        impl<T: PrimInt> $point<T> {
+            pub fn manhattan(&self, other: &Self) -> T {
+                T::zero() $(+ abs_diff(self.$c, other.$c))+
+            }
+
+            pub fn chebyshev(&self, other: &Self) -> T {
+                T::zero() $(.max(abs_diff(self.$c, other.$c)))+
+            }
+        }
+
+        // Comma-separated coordinates, either bare as in "3,-4" or named as
+        // in "x=3, y=-4"
+        impl<T: FromStr> FromStr for $point<T> {
+            type Err = ();
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut fields = s.split(',');
+                let point = $point {
+                    $($c: parse_coord(fields.next(), stringify!($c))?),+
+                };
+                match fields.next() {
+                    Some(_) => Err(()),
+                    None => Ok(point),
+                }
+            }
+        }
+    };
+}
+
+impl_ops!(Point2, Vec2, x, y);
+impl_ops!(Point3, Vec3, x, y, z);
+
+fn abs_diff<T: PrimInt>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+fn parse_coord<T: FromStr>(field: Option<&str>, name: &str) -> Result<T, ()> {
+    let field = field.ok_or(())?.trim();
+    let value = match field.split_once('=') {
+        Some((field_name, value)) if field_name.trim() == name => value,
+        Some(_) => return Err(()),
+        None => field,
+    };
+    value.trim().parse().map_err(|_| ())
+}
+
+impl<T: PrimInt> Point2<T> {
+    // Up, right, down and left, bounds-checked for unsigned coordinates
+    pub fn neighbours4(&self) -> impl Iterator<Item = Point2<T>> + '_ {
+        Direction::ALL.iter().filter_map(move |dir| self.step(*dir))
+    }
+
+    // None if the step leaves the range of T
+    pub fn step(&self, dir: Direction) -> Option<Point2<T>> {
+        let one = T::one();
+        Some(match dir {
+            Direction::Up => Point2::new(self.x, self.y.checked_sub(&one)?),
+            Direction::Right => Point2::new(self.x.checked_add(&one)?, self.y),
+            Direction::Down => Point2::new(self.x, self.y.checked_add(&one)?),
+            Direction::Left => Point2::new(self.x.checked_sub(&one)?, self.y),
+        })
+    }
+}
+
+// Grid directions with y increasing downwards, as in puzzle maps
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // Clockwise from up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn delta<T: PrimInt + Signed>(self) -> Vec2<T> {
+        let (zero, one) = (T::zero(), T::one());
+        match self {
+            Direction::Up => Vec2::new(zero, -one),
+            Direction::Right => Vec2::new(one, zero),
+            Direction::Down => Vec2::new(zero, one),
+            Direction::Left => Vec2::new(-one, zero),
+        }
+    }
+}
+
+// "U"/"R"/"D"/"L", compass points "N"/"E"/"S"/"W" or arrows "^"/">"/"v"/"<"
+impl FromStr for Direction {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" | "N" | "^" => Ok(Direction::Up),
+            "R" | "E" | ">" => Ok(Direction::Right),
+            "D" | "S" | "v" => Ok(Direction::Down),
+            "L" | "W" | "<" => Ok(Direction::Left),
+            _ => Err(()),
+        }
+    }
+}
+
+// Directions including diagonals, clockwise from up
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    // Turn by 45 degrees
+    pub fn turn_right(self) -> Self {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn delta<T: PrimInt + Signed>(self) -> Vec2<T> {
+        let (zero, one) = (T::zero(), T::one());
+        let (x, y) = match self {
+            Direction8::Up => (zero, -one),
+            Direction8::UpRight => (one, -one),
+            Direction8::Right => (one, zero),
+            Direction8::DownRight => (one, one),
+            Direction8::Down => (zero, one),
+            Direction8::DownLeft => (-one, one),
+            Direction8::Left => (-one, zero),
+            Direction8::UpLeft => (-one, -one),
+        };
+        Vec2::new(x, y)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        Direction8::ALL[dir as usize * 2]
+    }
+}
+
+// Inclusive on both corners
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BoundingBox<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: PrimInt> BoundingBox<T> {
+    pub fn from_points<'a>(
+        points: impl IntoIterator<Item = &'a Point2<T>>,
+    ) -> Option<Self>
+    where
+        T: 'a,
+    {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        let mut bbox = BoundingBox {
+            min: first,
+            max: first,
+        };
+        for point in points {
+            bbox.extend(point);
+        }
+        Some(bbox)
+    }
+
+    pub fn extend(&mut self, point: &Point2<T>) {
+        self.min =
+            Point2::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max =
+            Point2::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: &Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::one()
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::one()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> Vec<Point2<i32>> {
+        vec![Point2::new(1, 6), Point2::new(-2, 3), Point2::new(4, -1)]
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let p = Point2::new(1, 2);
+        let v = Vec2::new(3, -4);
+        assert_eq!(p + v, Point2::new(4, -2));
+        assert_eq!(p - v, Point2::new(-2, 6));
+        assert_eq!(Point2::new(4, -2) - p, v);
+        assert_eq!(v * 2 + v, Vec2::new(9, -12));
+        assert_eq!(-v - v, Vec2::new(-6, 8));
+
+        let mut q = Point3::new(1, 2, 3);
+        q += Vec3::new(1, 1, 1);
+        q -= Vec3::new(0, 0, 5);
+        assert_eq!(q, Point3::new(2, 3, -1));
+    }
+
+    #[test]
+    fn test_distances() {
+        let points = get_test_input();
+        assert_eq!(points[0].manhattan(&points[1]), 6);
+        assert_eq!(points[1].manhattan(&points[2]), 10);
+        assert_eq!(points[1].chebyshev(&points[2]), 6);
+        let a = Point3::new(1u8, 5, 2);
+        let b = Point3::new(4u8, 1, 2);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+    }
+
+    #[test]
+    fn test_direction() {
+        let dir = Direction::Up;
+        assert_eq!(dir.turn_right(), Direction::Right);
+        assert_eq!(dir.turn_left(), Direction::Left);
+        assert_eq!(dir.reverse(), Direction::Down);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Left.delta::<i32>(), Vec2::new(-1, 0));
+        assert_eq!("v".parse(), Ok(Direction::Down));
+        assert_eq!("x".parse::<Direction>(), Err(()));
+
+        assert_eq!(Direction8::UpLeft.turn_right(), Direction8::Up);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::from(Direction::Down), Direction8::Down);
+        assert_eq!(Direction8::DownLeft.delta::<i64>(), Vec2::new(-1, 1));
+    }
+
+    #[test]
+    fn test_step() {
+        let p = Point2::new(0u32, 1);
+        assert_eq!(p.step(Direction::Up), Some(Point2::new(0, 0)));
+        assert_eq!(p.step(Direction::Left), None);
+        let neighbours: Vec<_> = p.neighbours4().collect();
+        assert_eq!(
+            neighbours,
+            vec![Point2::new(0, 0), Point2::new(1, 1), Point2::new(0, 2)]
+        );
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = get_test_input();
+        let bbox = BoundingBox::from_points(&points).unwrap();
+        assert_eq!(bbox.min, Point2::new(-2, -1));
+        assert_eq!(bbox.max, Point2::new(4, 6));
+        assert_eq!((bbox.width(), bbox.height()), (7, 8));
+        assert!(bbox.contains(&Point2::new(0, 0)));
+        assert!(!bbox.contains(&Point2::new(5, 0)));
+        assert_eq!(BoundingBox::<i32>::from_points(&[]), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("3,-4".parse(), Ok(Point2::new(3, -4)));
+        assert_eq!("x=3, y=-4".parse(), Ok(Point2::new(3, -4)));
+        assert_eq!(" 1, 2 ,3".parse(), Ok(Point3::new(1, 2, 3)));
+        assert_eq!("x=1,y=2,z=3".parse(), Ok(Point3::new(1, 2, 3)));
+        for s in ["", "3", "3,4,5", "y=3,x=4", "x=3,y=", "a,b"] {
+            assert_eq!(s.parse::<Point2<i32>>(), Err(()));
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use ndarray::{s, Array2, ArrayView1};
+use std::collections::VecDeque;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+// Positions are (row, col), matching ndarray's [[row, col]] indexing
+pub type Pos = (usize, usize);
+
+const OFFSETS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+#[derive(Debug, PartialEq)]
+pub enum ParseGridError {
+    Empty,
+    Ragged { line: usize },
+    InvalidValue { line: usize },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "no rows"),
+            ParseGridError::Ragged { line } => {
+                write!(
+                    f,
+                    "line {}: row length differs from the first row",
+                    line
+                )
+            }
+            ParseGridError::InvalidValue { line } => {
+                write!(f, "line {}: invalid value", line)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(num_rows: usize, num_cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: Array2::from_elem((num_rows, num_cols), value),
+        }
+    }
+
+    // Cells in row-major order, None if there are not num_rows * num_cols
+    pub fn from_vec(
+        num_rows: usize,
+        num_cols: usize,
+        cells: Vec<T>,
+    ) -> Option<Self> {
+        Array2::from_shape_vec((num_rows, num_cols), cells)
+            .ok()
+            .map(|cells| Grid { cells })
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseGridError> {
+        let num_rows = rows.len();
+        let num_cols = rows.first().ok_or(ParseGridError::Empty)?.len();
+
+        let mut cells = Vec::with_capacity(num_rows * num_cols);
+        for (idx, row) in rows.into_iter().enumerate() {
+            if row.len() != num_cols {
+                return Err(ParseGridError::Ragged { line: idx + 1 });
+            }
+            cells.extend(row);
+        }
+
+        Grid::from_vec(num_rows, num_cols, cells).ok_or(ParseGridError::Empty)
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    pub fn as_array(&self) -> &Array2<T> {
+        &self.cells
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(pos)
+    }
+
+    // Bounds-checked step from a position, e.g. (-1, 0) for the cell above
+    pub fn offset(&self, pos: Pos, delta: (isize, isize)) -> Option<Pos> {
+        let row = pos.0.checked_add_signed(delta.0)?;
+        let col = pos.1.checked_add_signed(delta.1)?;
+        if row < self.num_rows() && col < self.num_cols() {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS4
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS8
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let num_cols = self.num_cols();
+        (0..self.num_rows() * num_cols)
+            .map(move |x| (x / num_cols, x % num_cols))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.indexed_iter()
+    }
+
+    pub fn row(&self, row: usize) -> ArrayView1<'_, T> {
+        self.cells.row(row)
+    }
+
+    pub fn column(&self, col: usize) -> ArrayView1<'_, T> {
+        self.cells.column(col)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = ArrayView1<'_, T>> {
+        self.cells.rows().into_iter()
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = ArrayView1<'_, T>> {
+        self.cells.columns().into_iter()
+    }
+
+    // Top-left to bottom-right
+    pub fn diagonal(&self) -> ArrayView1<'_, T> {
+        self.cells.diag()
+    }
+
+    // Top-right to bottom-left
+    pub fn anti_diagonal(&self) -> ArrayView1<'_, T> {
+        self.cells.slice(s![.., ..;-1]).into_diag()
+    }
+
+    // Sum of every cell, 0 for an empty grid
+    pub fn sum(&self) -> T
+    where
+        T: Clone + Sum,
+    {
+        self.cells.iter().cloned().sum()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.map(f),
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: self.cells.t().to_owned(),
+        }
+    }
+
+    // Mirror left to right
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: self.cells.slice(s![.., ..;-1]).to_owned(),
+        }
+    }
+
+    // Mirror top to bottom
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: self.cells.slice(s![..;-1, ..]).to_owned(),
+        }
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.flip_vertical().transpose()
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transpose().flip_vertical()
+    }
+
+    // Positions reachable from start through 4-neighbours, where a step from
+    // one cell to the next is allowed when connected(from, to) holds
+    pub fn flood_fill(
+        &self,
+        start: Pos,
+        connected: impl Fn(&T, &T) -> bool,
+    ) -> Vec<Pos> {
+        if self.get(start).is_none() {
+            return Vec::new();
+        }
+
+        let mut visited = Grid::new(self.num_rows(), self.num_cols(), false);
+        let mut region = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+
+        while let Some(pos) = queue.pop_front() {
+            region.push(pos);
+            for next in self.neighbours4(pos) {
+                if !visited[next] && connected(&self[pos], &self[next]) {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        region
+    }
+}
+
+impl Grid<char> {
+    // A character map such as "#..#\n.##.", one row per line
+    pub fn parse_chars(s: &str) -> Result<Self, ParseGridError> {
+        Grid::from_rows(s.lines().map(|line| line.chars().collect()).collect())
+    }
+}
+
+impl<T: FromStr> Grid<T> {
+    // Whitespace-separated values such as a bingo board, one row per line
+    pub fn parse_fields(s: &str) -> Result<Self, ParseGridError> {
+        let rows = s
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                line.split_whitespace()
+                    .map(|x| x.parse::<T>())
+                    .collect::<Result<Vec<T>, _>>()
+                    .map_err(|_| ParseGridError::InvalidValue { line: idx + 1 })
+            })
+            .collect::<Result<Vec<Vec<T>>, _>>()?;
+
+        Grid::from_rows(rows)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        &self.cells[pos]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        &mut self.cells[pos]
+    }
+}
+
+// Single character cells are printed as a character map, anything wider is
+// right-aligned in space-separated columns
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells = self.cells.map(|x| x.to_string());
+        let width = cells.iter().map(|x| x.chars().count()).max().unwrap_or(0);
+
+        for row in cells.rows() {
+            let row: Vec<String> =
+                row.iter().map(|x| format!("{:>1$}", x, width)).collect();
+            let separator = if width > 1 { " " } else { "" };
+            writeln!(f, "{}", row.join(separator))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> Grid<i32> {
+        Grid::parse_fields("1 2 3\n4 5 6").unwrap()
+    }
+
+    #[test]
+    fn test_parse_chars() {
+        let grid = Grid::parse_chars("#..\n.#.").unwrap();
+        assert_eq!((grid.num_rows(), grid.num_cols()), (2, 3));
+        assert_eq!(grid[(1, 1)], '#');
+        assert_eq!(
+            Grid::parse_chars("#..\n.#"),
+            Err(ParseGridError::Ragged { line: 2 })
+        );
+        assert_eq!(Grid::parse_chars(""), Err(ParseGridError::Empty));
+        assert_eq!(
+            ParseGridError::Ragged { line: 2 }.to_string(),
+            "line 2: row length differs from the first row"
+        );
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let grid = get_test_input();
+        assert_eq!(grid.row(1).to_vec(), vec![4, 5, 6]);
+        assert_eq!(grid.column(2).to_vec(), vec![3, 6]);
+        assert_eq!(
+            Grid::<i32>::parse_fields("1 2\n3 x"),
+            Err(ParseGridError::InvalidValue { line: 2 })
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = get_test_input();
+        assert_eq!(grid.get((1, 2)), Some(&6));
+        assert_eq!(grid.get((2, 0)), None);
+        *grid.get_mut((0, 0)).unwrap() = 9;
+        assert_eq!(grid[(0, 0)], 9);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = get_test_input();
+        let neighbours: Vec<Pos> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(neighbours, vec![(0, 1), (1, 0)]);
+        let neighbours: Vec<Pos> = grid.neighbours8((1, 1)).collect();
+        assert_eq!(neighbours, vec![(0, 0), (0, 1), (0, 2), (1, 2), (1, 0)]);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = Grid::<i32>::parse_fields("1 2 3\n4 5 6\n7 8 9").unwrap();
+        assert_eq!(grid.diagonal().to_vec(), vec![1, 5, 9]);
+        assert_eq!(grid.anti_diagonal().to_vec(), vec![3, 5, 7]);
+        let sums: Vec<i32> = grid.rows().map(|x| x.sum()).collect();
+        assert_eq!(sums, vec![6, 15, 24]);
+        let sums: Vec<i32> = grid.columns().map(|x| x.sum()).collect();
+        assert_eq!(sums, vec![12, 15, 18]);
+        assert_eq!(grid.sum(), 45);
+        assert_eq!(Grid::new(0, 3, 1).sum(), 0);
+        assert_eq!(grid.positions().nth(4), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = get_test_input();
+        let parse = |s| Grid::<i32>::parse_fields(s).unwrap();
+        assert_eq!(grid.transpose(), parse("1 4\n2 5\n3 6"));
+        assert_eq!(grid.flip_horizontal(), parse("3 2 1\n6 5 4"));
+        assert_eq!(grid.flip_vertical(), parse("4 5 6\n1 2 3"));
+        assert_eq!(grid.rotate_clockwise(), parse("4 1\n5 2\n6 3"));
+        assert_eq!(grid.rotate_counter_clockwise(), parse("3 6\n2 5\n1 4"));
+        assert_eq!(grid.map(|x| x * 2), parse("2 4 6\n8 10 12"));
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = Grid::parse_chars("..#\n.##\n#..").unwrap();
+        let mut region = grid.flood_fill((0, 0), |a, b| a == b);
+        region.sort();
+        assert_eq!(region, vec![(0, 0), (0, 1), (1, 0)]);
+        assert_eq!(grid.flood_fill((2, 2), |a, b| a == b).len(), 2);
+        assert!(grid.flood_fill((3, 0), |a, b| a == b).is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::parse_chars("#..\n.#.").unwrap();
+        assert_eq!(grid.to_string(), "#..\n.#.\n");
+        let grid = Grid::<i32>::parse_fields("22 13\n8 2").unwrap();
+        assert_eq!(grid.to_string(), "22 13\n 8  2\n");
+    }
+}
@@ -0,0 +1,318 @@
+use num_traits::PrimInt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+// Inclusive range of integers, min <= max always holds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    min: T,
+    max: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    pub fn new(min: T, max: T) -> Option<Self> {
+        if min <= max {
+            Some(Interval { min, max })
+        } else {
+            None
+        }
+    }
+
+    pub fn point(value: T) -> Self {
+        Interval {
+            min: value,
+            max: value,
+        }
+    }
+
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    pub fn max(&self) -> T {
+        self.max
+    }
+
+    // Number of integers in the interval, wide enough for any primitive
+    // integer except the full range of i128/u128
+    pub fn size(&self) -> u128 {
+        match (self.min.to_i128(), self.max.to_i128()) {
+            (Some(min), Some(max)) => (max - min) as u128 + 1,
+            _ => (self.max - self.min).to_u128().unwrap() + 1,
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    pub fn covers(&self, other: &Self) -> bool {
+        self.min <= other.min && other.max <= self.max
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.min <= other.max && other.min <= self.max
+    }
+
+    // Overlapping or directly next to each other, e.g. 1-3 and 4-6
+    pub fn touches(&self, other: &Self) -> bool {
+        !self.is_before(other) && !other.is_before(self)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Interval::new(self.min.max(other.min), self.max.min(other.max))
+    }
+
+    // None if the union would leave a gap
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.touches(other) {
+            Some(self.hull(other))
+        } else {
+            None
+        }
+    }
+
+    // Smallest interval containing both
+    pub fn hull(&self, other: &Self) -> Self {
+        Interval {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    // Entirely below other with at least one integer in between
+    fn is_before(&self, other: &Self) -> bool {
+        self.max < other.min && self.max + T::one() < other.min
+    }
+}
+
+// "min-max" where either bound may be negative, e.g. "-5--2"
+impl<T: PrimInt + FromStr> FromStr for Interval<T> {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sep = s.char_indices().skip(1).find(|(_, c)| *c == '-');
+        match sep {
+            Some((idx, _)) => {
+                match (T::from_str(&s[..idx]), T::from_str(&s[idx + 1..])) {
+                    (Ok(min), Ok(max)) => Interval::new(min, max).ok_or(()),
+                    (_, _) => Err(()),
+                }
+            }
+            None => Err(()),
+        }
+    }
+}
+
+// Disjoint intervals kept sorted, with touching intervals merged
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    // Number of disjoint intervals
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // Number of integers covered by the set
+    pub fn coverage(&self) -> u128 {
+        self.intervals.iter().map(|x| x.size()).sum()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|x| x.max < value);
+        self.intervals.get(idx).is_some_and(|x| x.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let lo = self.intervals.partition_point(|x| x.is_before(&interval));
+        let hi = self.intervals.partition_point(|x| !interval.is_before(x));
+
+        let merged = self.intervals[lo..hi]
+            .iter()
+            .fold(interval, |acc, x| acc.hull(x));
+        self.intervals.splice(lo..hi, [merged]);
+    }
+
+    pub fn remove(&mut self, interval: Interval<T>) {
+        let lo = self.intervals.partition_point(|x| x.max < interval.min);
+        let hi = self.intervals.partition_point(|x| x.min <= interval.max);
+        if lo == hi {
+            return;
+        }
+
+        let first = self.intervals[lo];
+        let last = self.intervals[hi - 1];
+        let mut remaining = Vec::new();
+        if first.min < interval.min {
+            remaining.push(Interval {
+                min: first.min,
+                max: interval.min - T::one(),
+            });
+        }
+        if interval.max < last.max {
+            remaining.push(Interval {
+                min: interval.max + T::one(),
+                max: last.max,
+            });
+        }
+        self.intervals.splice(lo..hi, remaining);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.extend(other.iter().copied());
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (&self.intervals[i], &other.intervals[j]);
+            if let Some(x) = a.intersection(b) {
+                intervals.push(x);
+            }
+            if a.max < b.max {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for interval in other.iter() {
+            result.remove(*interval);
+        }
+        result
+    }
+
+    // Uncovered intervals between the first and last covered integer
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.windows(2).map(|pair| Interval {
+            min: pair[0].max + T::one(),
+            max: pair[1].min - T::one(),
+        })
+    }
+}
+
+impl<T: PrimInt> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn interval(min: i32, max: i32) -> Interval<i32> {
+        Interval::new(min, max).unwrap()
+    }
+
+    fn get_test_input() -> IntervalSet<i32> {
+        [
+            interval(8, 10),
+            interval(1, 3),
+            interval(4, 5),
+            interval(2, 2),
+        ]
+        .iter()
+        .copied()
+        .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = interval(2, 6);
+        assert_eq!(Interval::new(6, 2), None);
+        assert_eq!(a.size(), 5);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).unwrap().size(), 1 << 64);
+        assert!(a.contains(2) && a.contains(6) && !a.contains(7));
+        assert!(a.covers(&interval(3, 4)) && !a.covers(&interval(3, 7)));
+        assert!(a.overlaps(&interval(6, 8)) && !a.overlaps(&interval(7, 8)));
+        assert!(a.touches(&interval(7, 8)) && !a.touches(&interval(8, 8)));
+        assert_eq!(a.intersection(&interval(5, 9)), Some(interval(5, 6)));
+        assert_eq!(a.intersection(&interval(7, 9)), None);
+        assert_eq!(a.union(&interval(7, 9)), Some(interval(2, 9)));
+        assert_eq!(a.union(&interval(8, 9)), None);
+    }
+
+    #[test]
+    fn test_interval_from_str() {
+        assert_eq!(Interval::from_str("2-4"), Ok(interval(2, 4)));
+        assert_eq!(Interval::from_str("-5--2"), Ok(interval(-5, -2)));
+        for s in ["", "-", "2", "2-", "-2", "4-2", "a-2"] {
+            assert_eq!(Interval::<i32>::from_str(s), Err(()));
+        }
+    }
+
+    #[test]
+    fn test_interval_set_insert() {
+        let set = get_test_input();
+        let intervals: Vec<_> = set.iter().copied().collect();
+        assert_eq!(intervals, vec![interval(1, 5), interval(8, 10)]);
+        assert_eq!(set.coverage(), 8);
+        assert!(set.contains(5) && !set.contains(6) && set.contains(8));
+        assert!(!set.contains(0) && !set.contains(11));
+    }
+
+    #[test]
+    fn test_interval_set_remove() {
+        let mut set = get_test_input();
+        set.remove(interval(3, 8));
+        let intervals: Vec<_> = set.iter().copied().collect();
+        assert_eq!(intervals, vec![interval(1, 2), interval(9, 10)]);
+        set.remove(interval(0, 20));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_interval_set_operations() {
+        let a = get_test_input();
+        let b: IntervalSet<i32> =
+            [interval(0, 1), interval(4, 9)].iter().copied().collect();
+        let to_vec = |set: IntervalSet<i32>| set.iter().copied().collect();
+
+        let union: Vec<_> = to_vec(a.union(&b));
+        assert_eq!(union, vec![interval(0, 10)]);
+        let intersection: Vec<_> = to_vec(a.intersection(&b));
+        assert_eq!(
+            intersection,
+            vec![interval(1, 1), interval(4, 5), interval(8, 9)]
+        );
+        let difference: Vec<_> = to_vec(a.difference(&b));
+        assert_eq!(difference, vec![interval(2, 3), interval(10, 10)]);
+        let gaps: Vec<_> = a.gaps().collect();
+        assert_eq!(gaps, vec![interval(6, 7)]);
+    }
+}
@@ -0,0 +1,12 @@
+pub mod bitset;
+pub mod consensus;
+pub mod geometry;
+pub mod grid;
+pub mod interval;
+pub mod number_theory;
+pub mod parse;
+pub mod search;
+pub mod simulation;
+pub mod solution;
+pub mod top_k;
+pub mod window;
@@ -0,0 +1,330 @@
+use num_traits::{PrimInt, Signed};
+
+// Always non-negative, gcd(0, 0) is 0. Panics on overflow, see checked_gcd.
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("gcd overflowed")
+}
+
+// None if the gcd doesn't fit in T, e.g. gcd(i32::MIN, 0)
+pub fn checked_gcd<T: PrimInt>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+    // a % -1 overflows for the minimum value, but is always 0
+    while b != T::zero() && !is_minus_one(b) {
+        (a, b) = (b, a % b);
+    }
+    if is_minus_one(b) {
+        return Some(T::one());
+    }
+    checked_abs(a)
+}
+
+// Panics on overflow, see checked_lcm
+pub fn lcm<T: PrimInt>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+pub fn checked_lcm<T: PrimInt>(a: T, b: T) -> Option<T> {
+    if a == T::zero() || b == T::zero() {
+        return Some(T::zero());
+    }
+    checked_abs(a / checked_gcd(a, b)?)?.checked_mul(&checked_abs(b)?)
+}
+
+// 0 for no values, panics on overflow
+pub fn gcd_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::zero(), gcd)
+}
+
+// 1 for no values, None on overflow
+pub fn lcm_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::one(), |acc, x| checked_lcm(acc, x))
+}
+
+pub fn checked_sum<T: PrimInt>(
+    values: impl IntoIterator<Item = T>,
+) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::zero(), |acc, x| acc.checked_add(&x))
+}
+
+pub fn checked_product<T: PrimInt>(
+    values: impl IntoIterator<Item = T>,
+) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::one(), |acc, x| acc.checked_mul(&x))
+}
+
+// Remainder in 0..|modulus|, also for negative values. The modulus must not
+// be 0.
+pub fn rem_euclid<T: PrimInt>(value: T, modulus: T) -> T {
+    assert!(modulus != T::zero(), "zero modulus");
+    // value % -1 overflows for the minimum value, but is always 0
+    if is_minus_one(modulus) {
+        return T::zero();
+    }
+
+    let rem = value % modulus;
+    if rem >= T::zero() {
+        rem
+    } else if modulus < T::zero() {
+        rem - modulus
+    } else {
+        rem + modulus
+    }
+}
+
+// base^exp mod modulus for moduli up to 2^64. The exponent must not be
+// negative and the modulus must be positive.
+pub fn mod_pow<T: PrimInt>(base: T, exp: T, modulus: T) -> T {
+    assert!(exp >= T::zero(), "negative exponent");
+    assert!(modulus > T::zero(), "modulus must be positive");
+    let mut base = to_u128(rem_euclid(base, modulus));
+    let modulus = to_u128(modulus);
+    let mut exp = to_u128(exp);
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    T::from(result).unwrap()
+}
+
+// (g, x, y) with a * x + b * y = g = gcd(a, b)
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while r != T::zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// x in 0..modulus with value * x = 1 mod modulus, if value and modulus are
+// coprime. None for a modulus of 0.
+pub fn mod_inv<T: PrimInt>(value: T, modulus: T) -> Option<T> {
+    if modulus == T::zero() {
+        return None;
+    }
+    let modulus = to_i128(modulus);
+    let (g, x, _) = extended_gcd(rem_euclid(to_i128(value), modulus), modulus);
+    if g == 1 {
+        T::from(rem_euclid(x, modulus))
+    } else {
+        None
+    }
+}
+
+// Smallest non-negative x with x = residue mod modulus for every
+// (residue, modulus) pair, together with the lcm of the moduli. The moduli
+// need not be coprime; None if the congruences contradict each other, a
+// modulus is 0 or the result does not fit in T.
+pub fn crt<T: PrimInt>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for (residue, modulus) in congruences {
+        if *modulus == T::zero() {
+            return None;
+        }
+        let modulus = to_i128(*modulus);
+        let residue = rem_euclid(to_i128(*residue), modulus);
+
+        let (g, p, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        // m * t = diff mod modulus, solved in the reduced modulus
+        let reduced = modulus / g;
+        let t = mul_mod(
+            rem_euclid(diff / g, reduced) as u128,
+            rem_euclid(p, reduced) as u128,
+            reduced as u128,
+        ) as i128;
+        x = x.checked_add(m.checked_mul(t)?)?;
+        m = m.checked_mul(reduced)?;
+        x = rem_euclid(x, m);
+    }
+
+    Some((T::from(x)?, T::from(m)?))
+}
+
+// Largest r with r * r <= n, n must not be negative
+pub fn isqrt<T: PrimInt>(n: T) -> T {
+    assert!(n >= T::zero(), "square root of negative number");
+    let n = to_u128(n);
+
+    // Float estimate, then correct the rounding error
+    let mut r = (n as f64).sqrt() as u128;
+    while r.checked_mul(r).is_none_or(|x| x > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).is_some_and(|x| x <= n) {
+        r += 1;
+    }
+
+    T::from(r).unwrap()
+}
+
+// a * b mod modulus by doubling and adding, so it doesn't overflow for
+// moduli up to 2^127. a and b must be less than the modulus.
+fn mul_mod(mut a: u128, mut b: u128, modulus: u128) -> u128 {
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % modulus;
+        }
+        a = (a + a) % modulus;
+        b >>= 1;
+    }
+    result
+}
+
+fn is_minus_one<T: PrimInt>(value: T) -> bool {
+    value < T::zero() && value + T::one() == T::zero()
+}
+
+fn checked_abs<T: PrimInt>(value: T) -> Option<T> {
+    if value < T::zero() {
+        T::zero().checked_sub(&value)
+    } else {
+        Some(value)
+    }
+}
+
+fn to_u128<T: PrimInt>(value: T) -> u128 {
+    value.to_u128().expect("value out of range for u128")
+}
+
+fn to_i128<T: PrimInt>(value: T) -> i128 {
+    value.to_i128().expect("value out of range for i128")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4, -6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(checked_lcm(200u8, 3), None);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(gcd_all(Vec::<u32>::new()), 0);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all([16u8, 17]), None);
+    }
+
+    #[test]
+    fn test_gcd_overflow() {
+        assert_eq!(checked_gcd(i32::MIN, 0), None);
+        assert_eq!(checked_gcd(i32::MIN, i32::MIN), None);
+        assert_eq!(checked_gcd(i32::MIN, 6), Some(2));
+        assert_eq!(checked_gcd(i32::MIN, -1), Some(1));
+        assert_eq!(checked_gcd(-1, i32::MIN), Some(1));
+        assert_eq!(checked_lcm(i32::MIN, 1), None);
+        assert_eq!(checked_lcm(i32::MIN, i32::MIN), None);
+        assert_eq!(rem_euclid(i32::MIN, -1), 0);
+        assert_eq!(rem_euclid(-7, i32::MIN), i32::MAX - 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd overflowed")]
+    fn test_gcd_min() {
+        gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(checked_sum([100u8, 100]), Some(200));
+        assert_eq!(checked_sum([200u8, 100]), None);
+        assert_eq!(checked_product([-4i8, 30]), Some(-120));
+        assert_eq!(checked_product([-5i8, 30]), None);
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(rem_euclid(-7, 3), 2);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(6, 9), None);
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(mod_inv(3, 0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be positive")]
+    fn test_mod_pow_zero_modulus() {
+        mod_pow(2, 3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "zero modulus")]
+    fn test_rem_euclid_zero_modulus() {
+        rem_euclid(7, 0);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt::<i32>(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(0u8, 16), (0, 17)]), None);
+        assert_eq!(crt(&[(2, 3), (0, 0)]), None);
+    }
+
+    #[test]
+    fn test_crt_large_moduli() {
+        let moduli = [(1u128 << 40) - 87, (1 << 40) + 15, (1 << 40) - 3];
+        let congruences =
+            [(12345, moduli[0]), (678, moduli[1]), (9, moduli[2])];
+        let (x, m) = crt(&congruences).unwrap();
+        assert_eq!(m, moduli.iter().product());
+        for (residue, modulus) in congruences {
+            assert_eq!(x % modulus, residue);
+        }
+
+        // Intermediate products near 2^200
+        assert_eq!(
+            crt(&[(1u128, 3), ((1 << 100) - 5, 1 << 100)]),
+            Some(((3 << 100) - 5, 3 << 100))
+        );
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16u8), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(i64::MAX), 3037000499);
+    }
+}
@@ -0,0 +1,501 @@
+use super::grid::Grid;
+use std::fmt;
+use std::str::FromStr;
+
+// Small parser combinators. A parser is any function taking the remaining
+// input and returning the parsed value and the input left after it, e.g.
+//
+//     let point = pair(signed::<i32>(), preceded(literal(","), signed()));
+//     let points = parse(lines(point), "1,2\n-3,4\n")?;
+//
+// Failures remember where in the input they happened, so parse() can report
+// the line and column even when a combinator handed a sub-slice (a single
+// line or group) to the parser that failed.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Failure {
+    // Address of the first byte that could not be parsed
+    at: usize,
+    expected: String,
+}
+
+pub type ParseResult<'a, T> = Result<(T, &'a str), Failure>;
+
+pub trait Parser<'a, T>: Fn(&'a str) -> ParseResult<'a, T> {}
+
+impl<'a, T, F> Parser<'a, T> for F where F: Fn(&'a str) -> ParseResult<'a, T> {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+pub fn fail<T>(input: &str, expected: impl Into<String>) -> ParseResult<'_, T> {
+    Err(failure(input, expected))
+}
+
+fn failure(input: &str, expected: impl Into<String>) -> Failure {
+    Failure {
+        at: input.as_ptr() as usize,
+        expected: expected.into(),
+    }
+}
+
+// Run a parser that has to consume the whole input, allowing for a single
+// trailing newline
+pub fn parse<'a, T>(
+    parser: impl Parser<'a, T>,
+    input: &'a str,
+) -> Result<T, ParseError> {
+    let result = parser(input).and_then(|(value, rest)| {
+        match rest.strip_suffix('\n').unwrap_or(rest) {
+            "" => Ok(value),
+            rest => Err(failure(rest, "end of input")),
+        }
+    });
+
+    // A failure outside of the input can only come from a parser handing
+    // back some other empty string, so it is taken as the end of the input
+    result.map_err(|failure| {
+        let offset = failure
+            .at
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| *offset <= input.len())
+            .unwrap_or(input.len());
+        let consumed = &input[..offset];
+        let line_start = consumed.rfind('\n').map_or(0, |idx| idx + 1);
+        ParseError {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            expected: failure.expected,
+        }
+    })
+}
+
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(expected) {
+        Some(rest) => Ok((&input[..expected.len()], rest)),
+        None => fail(input, format!("{:?}", expected)),
+    }
+}
+
+// A single character matching the predicate
+pub fn satisfy<'a>(
+    predicate: impl Fn(char) -> bool,
+    expected: &'static str,
+) -> impl Parser<'a, char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(c) if predicate(c) => Ok((c, &input[c.len_utf8()..])),
+        _ => fail(input, expected),
+    }
+}
+
+// Zero or more spaces or tabs, never newlines
+pub fn spaces<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let rest = input.trim_start_matches([' ', '\t']);
+        Ok((&input[..input.len() - rest.len()], rest))
+    }
+}
+
+pub fn spaces1<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match spaces()(input)? {
+        ("", _) => fail(input, "space"),
+        result => Ok(result),
+    }
+}
+
+pub fn unsigned<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let len = input.len() - input.trim_start_matches(is_digit).len();
+        if len == 0 {
+            return fail(input, "integer");
+        }
+        match input[..len].parse() {
+            Ok(value) => Ok((value, &input[len..])),
+            Err(_) => fail(input, "integer in range"),
+        }
+    }
+}
+
+// Integer with an optional "+" or "-" sign
+pub fn signed<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let sign = match input.as_bytes().first() {
+            Some(b'+') | Some(b'-') => 1,
+            _ => 0,
+        };
+        let len = input[sign..].len()
+            - input[sign..].trim_start_matches(is_digit).len();
+        if len == 0 {
+            return fail(input, "integer");
+        }
+        match input[..sign + len].parse() {
+            Ok(value) => Ok((value, &input[sign + len..])),
+            Err(_) => fail(input, "integer in range"),
+        }
+    }
+}
+
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
+pub fn map<'a, A, B>(
+    parser: impl Parser<'a, A>,
+    f: impl Fn(A) -> B,
+) -> impl Parser<'a, B> {
+    move |input: &'a str| {
+        let (value, rest) = parser(input)?;
+        Ok((f(value), rest))
+    }
+}
+
+// As map, failing at the start of the parsed value if f returns None
+pub fn map_opt<'a, A, B>(
+    parser: impl Parser<'a, A>,
+    f: impl Fn(A) -> Option<B>,
+    expected: &'static str,
+) -> impl Parser<'a, B> {
+    move |input: &'a str| {
+        let (value, rest) = parser(input)?;
+        match f(value) {
+            Some(value) => Ok((value, rest)),
+            None => fail(input, expected),
+        }
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: &'a str| {
+        let (a, rest) = first(input)?;
+        let (b, rest) = second(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+// Keep the result of the second parser
+pub fn preceded<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+// Keep the result of the first parser
+pub fn terminated<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+pub fn delimited<'a, A, B, C>(
+    open: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, B> {
+    preceded(open, terminated(parser, close))
+}
+
+// Try the second parser if the first one fails. The failure that got
+// furthest into the input is reported.
+pub fn alt<'a, T>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    move |input: &'a str| match first(input) {
+        Ok(result) => Ok(result),
+        Err(err1) => match second(input) {
+            Ok(result) => Ok(result),
+            Err(err2) if err2.at >= err1.at => Err(err2),
+            Err(_) => Err(err1),
+        },
+    }
+}
+
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &'a str| match parser(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+// Zero or more
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: &'a str| {
+        let mut values = Vec::new();
+        while let Ok((value, rest)) = parser(input) {
+            if rest.len() == input.len() {
+                break;
+            }
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+// One or more items. A separator not followed by an item is left unparsed.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (value, mut input) = item(input)?;
+        let mut values = vec![value];
+        while let Ok((value, rest)) =
+            separator(input).and_then(|(_, rest)| item(rest))
+        {
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+// Apply the parser to the text before the next newline (or the end of the
+// input), which it must consume completely. The newline is skipped.
+pub fn line<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let (line, rest) = input
+            .split_once('\n')
+            .unwrap_or((input, &input[input.len()..]));
+        let (value, line_rest) = parser(line)?;
+        if !line_rest.is_empty() {
+            return fail(line_rest, "end of line");
+        }
+        Ok((value, rest))
+    }
+}
+
+// One or more non-empty lines, stopping before a blank line
+pub fn lines<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    let parser = line(parser);
+    move |mut input: &'a str| {
+        let mut values = Vec::new();
+        loop {
+            if input.is_empty() || input.starts_with('\n') {
+                if values.is_empty() {
+                    return fail(input, "line");
+                }
+                return Ok((values, input));
+            }
+            let (value, rest) = parser(input)?;
+            values.push(value);
+            input = rest;
+        }
+    }
+}
+
+// Blocks of lines separated by blank lines. Each block is handed to the
+// parser without its final newline and must be consumed completely.
+pub fn groups<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let mut values = Vec::new();
+        let mut rest = input.trim_start_matches('\n');
+        while !rest.is_empty() {
+            let (group, next) = rest
+                .split_once("\n\n")
+                .unwrap_or((rest, &rest[rest.len()..]));
+            let group = group.strip_suffix('\n').unwrap_or(group);
+            let (value, group_rest) = parser(group)?;
+            if !group_rest.is_empty() {
+                return fail(group_rest, "end of group");
+            }
+            values.push(value);
+            rest = next.trim_start_matches('\n');
+        }
+
+        if values.is_empty() {
+            return fail(input, "group");
+        }
+        Ok((values, rest))
+    }
+}
+
+// Split the current line into cells of the given width, the last one may be
+// shorter, and parse each cell completely. The newline is not consumed.
+// Panics if the width is 0.
+pub fn fixed_width<'a, T>(
+    width: usize,
+    cell: impl Parser<'a, T>,
+) -> impl Parser<'a, Vec<T>> {
+    assert!(width > 0, "Column width must be positive");
+    move |input: &'a str| {
+        let end = input.find('\n').unwrap_or(input.len());
+        let mut line = &input[..end];
+        let mut values = Vec::new();
+        while !line.is_empty() {
+            let split = line
+                .char_indices()
+                .nth(width)
+                .map_or(line.len(), |(idx, _)| idx);
+            let (value, cell_rest) = cell(&line[..split])?;
+            if !cell_rest.is_empty() {
+                return fail(cell_rest, "end of column");
+            }
+            values.push(value);
+            line = &line[split..];
+        }
+        Ok((values, &input[end..]))
+    }
+}
+
+// Character grid of equally long lines, stopping before a blank line
+pub fn grid<'a, T>(
+    cell: impl Fn(char) -> Option<T>,
+    expected: &'static str,
+) -> impl Parser<'a, Grid<T>> {
+    let row = many(map_opt(satisfy(|_| true, expected), cell, expected));
+    let rows = lines(row);
+    move |input: &'a str| {
+        let (rows, rest) = rows(input)?;
+        let width = rows[0].len();
+        if let Some(idx) = rows.iter().position(|row| row.len() != width) {
+            let line = input.split('\n').nth(idx).unwrap();
+            return fail(line, format!("row of width {}", width));
+        }
+        let grid = Grid::from_rows(rows).unwrap();
+        Ok((grid, rest))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> &'static str {
+        "1,2\n-3,4\n\n5,6\n"
+    }
+
+    fn point<'a>() -> impl Parser<'a, (i32, i32)> {
+        pair(signed(), preceded(literal(","), signed()))
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(unsigned::<u8>()("255x"), Ok((255, "x")));
+        assert_eq!(signed::<i8>()("-128"), Ok((-128, "")));
+        assert_eq!(signed::<i8>()("+5"), Ok((5, "")));
+        let err = parse(unsigned::<u8>(), "256").unwrap_err();
+        assert_eq!(err.expected, "integer in range");
+        let err = parse(signed::<i32>(), "-").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (1, "integer"));
+    }
+
+    #[test]
+    fn test_combinators() {
+        assert_eq!(parse(point(), "-1,2"), Ok((-1, 2)));
+        let list = separated(unsigned::<u32>(), literal(","));
+        assert_eq!(parse(&list, "1,2,3"), Ok(vec![1, 2, 3]));
+        assert_eq!(list("1,2,"), Ok((vec![1, 2], ",")));
+        let words = separated(alt(literal("up"), literal("down")), spaces1());
+        assert_eq!(parse(words, "up  down"), Ok(vec!["up", "down"]));
+        assert_eq!(opt(literal("x"))("y"), Ok((None, "y")));
+        assert_eq!(many(literal("ab"))("ababa"), Ok((vec!["ab", "ab"], "a")));
+        let bracketed = delimited(literal("["), unsigned::<u8>(), literal("]"));
+        assert_eq!(parse(bracketed, "[7]"), Ok(7));
+    }
+
+    #[test]
+    fn test_lines_and_groups() {
+        let result = parse(groups(lines(point())), get_test_input());
+        assert_eq!(result, Ok(vec![vec![(1, 2), (-3, 4)], vec![(5, 6)]]));
+
+        let err = parse(groups(lines(point())), "1,2\n\n5,6\n7;8").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                line: 4,
+                column: 2,
+                expected: "\",\"".to_string()
+            }
+        );
+
+        let block =
+            pair(lines(point()), preceded(literal("\n"), lines(point())));
+        let (first, second) = parse(block, get_test_input()).unwrap();
+        assert_eq!((first.len(), second.len()), (2, 1));
+    }
+
+    #[test]
+    fn test_failure_at_end_of_input() {
+        // The last line and group leave an empty remainder at the end of the
+        // input for the next parser to fail on
+        let err = parse(pair(line(unsigned::<u32>()), unsigned::<u32>()), "5")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+        let block = pair(
+            lines(unsigned::<u32>()),
+            preceded(literal("\n"), unsigned::<u32>()),
+        );
+        let err = parse(block, "1\n2").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = parse(pair(groups(point()), point()), "1,2").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+
+        // Anywhere else is also taken as the end of the input
+        let elsewhere = |_: &str| fail::<u32>("elsewhere", "nothing");
+        let err = parse(elsewhere, "12\n3").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn test_fixed_width() {
+        let cell = terminated(
+            alt(
+                map(
+                    delimited(
+                        literal("["),
+                        satisfy(|c| c.is_ascii_uppercase(), "crate"),
+                        literal("]"),
+                    ),
+                    Some,
+                ),
+                map(literal("   "), |_| None),
+            ),
+            opt(literal(" ")),
+        );
+        let row = fixed_width(4, cell);
+        assert_eq!(
+            parse(&row, "    [D] [E]"),
+            Ok(vec![None, Some('D'), Some('E')])
+        );
+        let err = parse(&row, "[A] [b]").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+    }
+
+    #[test]
+    #[should_panic(expected = "Column width must be positive")]
+    fn test_fixed_width_zero() {
+        let _ = fixed_width(0, opt(literal("x")));
+    }
+
+    #[test]
+    fn test_grid() {
+        let digits = grid(|c| c.to_digit(10), "digit");
+        let result = parse(&digits, "123\n456\n").unwrap();
+        assert_eq!(result.row(1).to_vec(), vec![4, 5, 6]);
+        let err = parse(&digits, "123\n45\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = parse(&digits, "123\n4x6").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.to_string(), "line 2, column 2: expected end of line");
+    }
+}
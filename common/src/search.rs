@@ -0,0 +1,362 @@
+use num_traits::Zero;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+// Shortest path from start to the first node satisfying is_goal, start and
+// goal included, counting every edge as one step
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start.clone()]);
+    parents.insert(start, None);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+
+        for next in successors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+// Number of steps to every node reachable from start
+pub fn bfs_distances<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::from([(start.clone(), 0)]);
+    distances.insert(start, 0);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+// Cheapest path to the first node satisfying is_goal and its total cost.
+// Edge costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::zero(), is_goal)
+}
+
+// Cost of the cheapest path to every node reachable from start
+pub fn dijkstra_distances<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut distances = HashMap::new();
+    let mut heap = BinaryHeap::from([State {
+        priority: C::zero(),
+        cost: C::zero(),
+        node: start.clone(),
+    }]);
+    distances.insert(start, C::zero());
+
+    while let Some(State { cost, node, .. }) = heap.pop() {
+        if distances.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            if distances.get(&next).is_none_or(|best| next_cost < *best) {
+                distances.insert(next.clone(), next_cost);
+                heap.push(State {
+                    priority: next_cost,
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    distances
+}
+
+// As dijkstra, guided by a heuristic that must never overestimate the
+// remaining cost to a goal
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut parents = HashMap::new();
+    let mut costs = HashMap::new();
+    let mut heap = BinaryHeap::from([State {
+        priority: heuristic(&start),
+        cost: C::zero(),
+        node: start.clone(),
+    }]);
+    parents.insert(start.clone(), None);
+    costs.insert(start, C::zero());
+
+    while let Some(State { cost, node, .. }) = heap.pop() {
+        if costs.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((reconstruct_path(&parents, node), cost));
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), Some(node.clone()));
+                heap.push(State {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+// Follow parent links back from goal to the node without a parent
+pub fn reconstruct_path<N>(parents: &HashMap<N, Option<N>>, goal: N) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+{
+    let mut path = vec![goal];
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Cycle {
+    // Index of the first state that repeats
+    pub start: usize,
+    pub length: usize,
+}
+
+// Floyd's tortoise and hare over the sequence start, step(start), ...
+// which must eventually repeat. Only two states are kept at a time.
+pub fn find_cycle<N: Eq>(start: N, mut step: impl FnMut(&N) -> N) -> Cycle {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut cycle_start = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        cycle_start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+// Min-heap entry ordered by priority only
+struct State<C, N> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<C: Ord, N> Ord for State<C, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<C: Ord, N> PartialOrd for State<C, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord, N> PartialEq for State<C, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<C: Ord, N> Eq for State<C, N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    //   a -1- b -1- c
+    //   |           |
+    //   5           1
+    //   |           |
+    //   d ----1---- e     f
+    fn get_test_input() -> HashMap<char, Vec<(char, u32)>> {
+        let edges = [
+            ('a', 'b', 1),
+            ('b', 'c', 1),
+            ('c', 'e', 1),
+            ('a', 'd', 5),
+            ('d', 'e', 1),
+        ];
+
+        let mut graph: HashMap<char, Vec<(char, u32)>> = HashMap::new();
+        for (from, to, cost) in edges {
+            graph.entry(from).or_default().push((to, cost));
+            graph.entry(to).or_default().push((from, cost));
+        }
+        graph.insert('f', Vec::new());
+        graph
+    }
+
+    #[test]
+    fn test_bfs() {
+        let graph = get_test_input();
+        let successors = |x: &char| graph[x].iter().map(|(next, _)| *next);
+        assert_eq!(
+            bfs('a', successors, |x| *x == 'e'),
+            Some(vec!['a', 'd', 'e'])
+        );
+        assert_eq!(bfs('a', successors, |x| *x == 'a'), Some(vec!['a']));
+        assert_eq!(bfs('a', successors, |x| *x == 'f'), None);
+
+        let distances = bfs_distances('a', successors);
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&'c'], 2);
+        assert_eq!(distances[&'e'], 2);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let graph = get_test_input();
+        let successors = |x: &char| graph[x].clone();
+        assert_eq!(
+            dijkstra('a', successors, |x| *x == 'd'),
+            Some((vec!['a', 'b', 'c', 'e', 'd'], 4))
+        );
+        assert_eq!(dijkstra('f', successors, |x| *x == 'a'), None);
+
+        let distances = dijkstra_distances('a', successors);
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&'d'], 4);
+        assert_eq!(distances[&'e'], 3);
+    }
+
+    #[test]
+    fn test_astar() {
+        // Open 4x4 grid with a wall, Manhattan distance heuristic
+        let walls = [(1, 0), (1, 1), (1, 2)];
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .iter()
+                .copied()
+                .filter(|(x, y)| (0..4).contains(x) && (0..4).contains(y))
+                .filter(|pos| !walls.contains(pos))
+                .map(|pos| (pos, 1))
+                .collect::<Vec<_>>()
+        };
+        let goal = (2, 0);
+        let heuristic =
+            |&(x, y): &(i32, i32)| (x - goal.0).abs() + (y - goal.1).abs();
+
+        let (path, cost) =
+            astar((0, 0), successors, heuristic, |x| *x == goal).unwrap();
+        assert_eq!(cost, 8);
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+    }
+
+    #[test]
+    fn test_reconstruct_path() {
+        let parents = HashMap::from([(1, None), (2, Some(1)), (3, Some(2))]);
+        assert_eq!(reconstruct_path(&parents, 3), vec![1, 2, 3]);
+        assert_eq!(reconstruct_path(&parents, 1), vec![1]);
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 3 ...
+        let step = |x: &u32| if *x == 5 { 3 } else { x + 1 };
+        assert_eq!(
+            find_cycle(0, step),
+            Cycle {
+                start: 3,
+                length: 3
+            }
+        );
+        assert_eq!(
+            find_cycle(7, |_| 7),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+        assert_eq!(
+            find_cycle(1, |x| x * 2 % 7),
+            Cycle {
+                start: 0,
+                length: 3
+            }
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use super::search::Cycle;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub trait Simulation {
+    type Key: Eq + Hash;
+
+    fn step(&mut self);
+
+    // Everything that determines the following steps. Two states with the
+    // same key must evolve identically.
+    fn key(&self) -> Self::Key;
+}
+
+// Advance the simulation by the given number of steps. Once a state repeats
+// the remaining whole cycles are skipped, so huge step counts are cheap for
+// simulations that settle into a loop. Returns the cycle if one was found.
+pub fn fast_forward<S: Simulation>(sim: &mut S, steps: usize) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+
+    for curr_step in 0..steps {
+        if let Some(cycle_start) = seen.insert(sim.key(), curr_step) {
+            let cycle = Cycle {
+                start: cycle_start,
+                length: curr_step - cycle_start,
+            };
+            for _ in 0..(steps - curr_step) % cycle.length {
+                sim.step();
+            }
+            return Some(cycle);
+        }
+
+        sim.step();
+    }
+
+    None
+}
+
+// Cache for recursive functions. The function receives the cache so that
+// recursive calls go through it as well, e.g.
+//
+//     fn count(memo: &mut Memo<u32, u64>, n: u32) -> u64 {
+//         memo.get(n, |memo, n| if n < 2 { 1 } else { count(memo, n - 1) })
+//     }
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn get(&mut self, key: K, f: impl FnOnce(&mut Self, K) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+
+        let value = f(self, key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Counter that runs 0, 1, ..., 9 and then loops over 5..10
+    struct Counter {
+        value: u32,
+        steps: usize,
+    }
+
+    impl Simulation for Counter {
+        type Key = u32;
+
+        fn step(&mut self) {
+            self.value = if self.value == 9 { 5 } else { self.value + 1 };
+            self.steps += 1;
+        }
+
+        fn key(&self) -> u32 {
+            self.value
+        }
+    }
+
+    fn get_test_input() -> Counter {
+        Counter { value: 0, steps: 0 }
+    }
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get(n, |memo, n| match n {
+            0 | 1 => n,
+            _ => fibonacci(memo, n - 1) + fibonacci(memo, n - 2),
+        })
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let mut sim = get_test_input();
+        assert_eq!(fast_forward(&mut sim, 7), None);
+        assert_eq!((sim.value, sim.steps), (7, 7));
+
+        let mut sim = get_test_input();
+        let cycle = fast_forward(&mut sim, 1_000_000_000_003);
+        assert_eq!(
+            cycle,
+            Some(Cycle {
+                start: 5,
+                length: 5
+            })
+        );
+        assert_eq!(sim.value, 8);
+        assert!(sim.steps < 20);
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tag {
+    Parsing,
+    Simulation,
+    Intervals,
+    Grid,
+    Sets,
+    Sorting,
+    Bits,
+    SlidingWindow,
+}
+
+impl Tag {
+    fn name(&self) -> &'static str {
+        match self {
+            Tag::Parsing => "parsing",
+            Tag::Simulation => "simulation",
+            Tag::Intervals => "intervals",
+            Tag::Grid => "grid",
+            Tag::Sets => "sets",
+            Tag::Sorting => "sorting",
+            Tag::Bits => "bits",
+            Tag::SlidingWindow => "sliding-window",
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Tag {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parsing" => Ok(Tag::Parsing),
+            "simulation" => Ok(Tag::Simulation),
+            "intervals" => Ok(Tag::Intervals),
+            "grid" => Ok(Tag::Grid),
+            "sets" => Ok(Tag::Sets),
+            "sorting" => Ok(Tag::Sorting),
+            "bits" => Ok(Tag::Bits),
+            "sliding-window" => Ok(Tag::SlidingWindow),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub tags: &'static [Tag],
+    // Accepted answer for each part, None until the part is solved
+    pub answers: [Option<&'static str>; 2],
+    pub main: fn(),
+}
+
+impl Solution {
+    pub fn url(&self) -> String {
+        format!("https://adventofcode.com/{}/day/{}", self.year, self.day)
+    }
+
+    pub fn stars(&self) -> usize {
+        self.answers.iter().filter(|x| x.is_some()).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> Solution {
+        Solution {
+            year: 2022,
+            day: 4,
+            title: "Camp Cleanup",
+            tags: &[Tag::Parsing, Tag::Intervals],
+            answers: [Some("2"), None],
+            main: || (),
+        }
+    }
+
+    #[test]
+    fn test_solution() {
+        let solution = get_test_input();
+        assert_eq!(solution.url(), "https://adventofcode.com/2022/day/4");
+        assert_eq!(solution.stars(), 1);
+    }
+
+    #[test]
+    fn test_tag_from_str() {
+        for tag in [Tag::Parsing, Tag::SlidingWindow, Tag::Bits] {
+            assert_eq!(Tag::from_str(&tag.to_string()), Ok(tag));
+        }
+        assert_eq!(Tag::from_str("Parsing"), Err(()));
+    }
+}
@@ -0,0 +1,178 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+// Heap entry ordered by key only. On equal keys the earlier item ranks
+// higher, so results are stable.
+struct Entry<K, T> {
+    key: K,
+    idx: usize,
+    item: T,
+}
+
+impl<K: Ord, T> Ord for Entry<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| other.idx.cmp(&self.idx))
+    }
+}
+
+impl<K: Ord, T> PartialOrd for Entry<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, T> PartialEq for Entry<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Ord, T> Eq for Entry<K, T> {}
+
+// The k (key, item) pairs with the largest keys, largest first, each with
+// its position in the input. Only k entries are kept in the heap at a time,
+// and no more are allocated up front than the input says it holds.
+fn select<K: Ord, T>(
+    iter: impl Iterator<Item = (K, T)>,
+    k: usize,
+) -> Vec<Entry<K, T>> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let capacity = k.min(iter.size_hint().0).saturating_add(1);
+    let mut heap = BinaryHeap::with_capacity(capacity);
+    for (idx, (key, item)) in iter.enumerate() {
+        let entry = Entry { key, idx, item };
+        if heap.len() < k {
+            heap.push(Reverse(entry));
+        } else if heap.peek().is_some_and(|Reverse(min)| entry > *min) {
+            heap.pop();
+            heap.push(Reverse(entry));
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(entry)| entry)
+        .collect()
+}
+
+pub fn top_k<T: Ord>(iter: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    select(iter.into_iter().map(|x| (x, ())), k)
+        .into_iter()
+        .map(|entry| entry.key)
+        .collect()
+}
+
+pub fn top_k_by_key<T, K: Ord>(
+    iter: impl IntoIterator<Item = T>,
+    k: usize,
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<T> {
+    select(iter.into_iter().map(|x| (key(&x), x)), k)
+        .into_iter()
+        .map(|entry| entry.item)
+        .collect()
+}
+
+// As top_k, paired with the position of each item in the input
+pub fn top_k_indexed<T: Ord>(
+    iter: impl IntoIterator<Item = T>,
+    k: usize,
+) -> Vec<(usize, T)> {
+    select(iter.into_iter().map(|x| (x, ())), k)
+        .into_iter()
+        .map(|entry| (entry.idx, entry.key))
+        .collect()
+}
+
+pub fn top_k_indexed_by_key<T, K: Ord>(
+    iter: impl IntoIterator<Item = T>,
+    k: usize,
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<(usize, T)> {
+    select(iter.into_iter().map(|x| (key(&x), x)), k)
+        .into_iter()
+        .map(|entry| (entry.idx, entry.item))
+        .collect()
+}
+
+// Iterator adapter, e.g. calories.iter().top_k(3)
+pub trait TopK: Iterator + Sized {
+    fn top_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        top_k(self, k)
+    }
+
+    fn top_k_by_key<K: Ord>(
+        self,
+        k: usize,
+        key: impl FnMut(&Self::Item) -> K,
+    ) -> Vec<Self::Item> {
+        top_k_by_key(self, k, key)
+    }
+
+    fn top_k_indexed(self, k: usize) -> Vec<(usize, Self::Item)>
+    where
+        Self::Item: Ord,
+    {
+        top_k_indexed(self, k)
+    }
+
+    fn top_k_indexed_by_key<K: Ord>(
+        self,
+        k: usize,
+        key: impl FnMut(&Self::Item) -> K,
+    ) -> Vec<(usize, Self::Item)> {
+        top_k_indexed_by_key(self, k, key)
+    }
+}
+
+impl<I: Iterator> TopK for I {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> Vec<i32> {
+        vec![6000, 4000, 11000, 24000, 10000, 11000]
+    }
+
+    #[test]
+    fn test_top_k() {
+        let values = get_test_input();
+        assert_eq!(top_k(values.iter().copied(), 3), vec![24000, 11000, 11000]);
+        assert_eq!(values.iter().top_k(1), vec![&24000]);
+        assert!(values.iter().top_k(0).is_empty());
+        assert_eq!(values.iter().top_k(10).len(), values.len());
+        assert_eq!(top_k(vec![1, 2, 3], usize::MAX), vec![3, 2, 1]);
+        assert_eq!(top_k((1..4).filter(|_| true), usize::MAX), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_top_k_by_key() {
+        let values = get_test_input();
+        let smallest = values.iter().top_k_by_key(2, |x| Reverse(**x));
+        assert_eq!(smallest, vec![&4000, &6000]);
+        let words = ["a", "ccc", "bb", "ddd"];
+        assert_eq!(words.iter().top_k_by_key(2, |x| x.len()), [&"ccc", &"ddd"]);
+    }
+
+    #[test]
+    fn test_top_k_indexed() {
+        let values = get_test_input();
+        assert_eq!(
+            values.iter().copied().top_k_indexed(3),
+            vec![(3, 24000), (2, 11000), (5, 11000)]
+        );
+        assert_eq!(
+            top_k_indexed_by_key(values, 2, |x| Reverse(*x)),
+            vec![(1, 4000), (0, 6000)]
+        );
+    }
+}
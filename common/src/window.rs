@@ -0,0 +1,408 @@
+use num_traits::ToPrimitive;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Window<T> {
+    pub sum: T,
+    pub min: T,
+    pub max: T,
+    pub mean: f64,
+}
+
+// How the sum of a window follows the readings entering and leaving it, in
+// O(1) per reading. Integers keep an exact running sum.
+pub trait WindowSum: Copy {
+    type Acc: Default;
+
+    fn add(acc: &mut Self::Acc, value: Self);
+    fn remove(acc: &mut Self::Acc, value: Self);
+    fn total(acc: &Self::Acc) -> Self;
+}
+
+// Readings are removed before the next one is added, so the sum never
+// exceeds that of a full window, e.g. for unsigned readings near the maximum
+macro_rules! running_sum {
+    ($($t:ty)*) => {$(
+        impl WindowSum for $t {
+            type Acc = $t;
+
+            fn add(acc: &mut Self, value: Self) {
+                *acc += value;
+            }
+
+            fn remove(acc: &mut Self, value: Self) {
+                *acc -= value;
+            }
+
+            fn total(acc: &Self) -> Self {
+                *acc
+            }
+        }
+    )*};
+}
+
+// Running float sum with Neumaier compensation, so small readings next to
+// large ones aren't lost once the large ones leave the window. NaNs and
+// infinities are counted instead of summed, as a float sum never drops them
+// once added.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CompensatedSum<T> {
+    sum: T,
+    compensation: T,
+    nans: usize,
+    infinities: usize,
+    neg_infinities: usize,
+}
+
+macro_rules! compensated_sum {
+    ($($t:ty)*) => {$(
+        impl CompensatedSum<$t> {
+            fn update(&mut self, value: $t, removed: bool) {
+                let counter = if value.is_nan() {
+                    Some(&mut self.nans)
+                } else if value == <$t>::INFINITY {
+                    Some(&mut self.infinities)
+                } else if value == <$t>::NEG_INFINITY {
+                    Some(&mut self.neg_infinities)
+                } else {
+                    None
+                };
+
+                match counter {
+                    Some(counter) if removed => *counter -= 1,
+                    Some(counter) => *counter += 1,
+                    None => {
+                        let value = if removed { -value } else { value };
+                        let sum = self.sum + value;
+                        self.compensation += if self.sum.abs() >= value.abs() {
+                            (self.sum - sum) + value
+                        } else {
+                            (value - sum) + self.sum
+                        };
+                        self.sum = sum;
+                    }
+                }
+            }
+        }
+
+        impl WindowSum for $t {
+            type Acc = CompensatedSum<$t>;
+
+            fn add(acc: &mut Self::Acc, value: Self) {
+                acc.update(value, false);
+            }
+
+            fn remove(acc: &mut Self::Acc, value: Self) {
+                acc.update(value, true);
+            }
+
+            fn total(acc: &Self::Acc) -> Self {
+                match (acc.nans, acc.infinities, acc.neg_infinities) {
+                    (0, 0, 0) => acc.sum + acc.compensation,
+                    (0, _, 0) => <$t>::INFINITY,
+                    (0, 0, _) => <$t>::NEG_INFINITY,
+                    _ => <$t>::NAN,
+                }
+            }
+        }
+    )*};
+}
+
+running_sum!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+compensated_sum!(f32 f64);
+
+// Every full window of a fixed size over a stream of readings. Min/max
+// candidates are kept in monotonic deques, so each window costs amortized
+// O(1). A window holding a NaN reading has a NaN sum, min, max and
+// mean.
+pub struct SlidingWindows<I>
+where
+    I: Iterator,
+    I::Item: WindowSum,
+{
+    iter: I,
+    size: usize,
+    idx: usize,
+    values: VecDeque<I::Item>,
+    sum: <I::Item as WindowSum>::Acc,
+    // (index, value) pairs, increasing for mins and decreasing for maxs
+    mins: VecDeque<(usize, I::Item)>,
+    maxs: VecDeque<(usize, I::Item)>,
+    // Latest reading that can't be compared with itself, i.e. a NaN
+    unordered: Option<(usize, I::Item)>,
+}
+
+impl<I> Iterator for SlidingWindows<I>
+where
+    I: Iterator,
+    I::Item: PartialOrd + WindowSum + ToPrimitive,
+{
+    type Item = Window<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let value = self.iter.next()?;
+            let idx = self.idx;
+            self.idx += 1;
+
+            if self.values.len() == self.size {
+                let oldest = self.values.pop_front().unwrap();
+                WindowSum::remove(&mut self.sum, oldest);
+            }
+            self.values.push_back(value);
+            WindowSum::add(&mut self.sum, value);
+
+            if value.partial_cmp(&value).is_none() {
+                self.unordered = Some((idx, value));
+            } else {
+                while self.mins.back().is_some_and(|(_, x)| *x >= value) {
+                    self.mins.pop_back();
+                }
+                self.mins.push_back((idx, value));
+                while self.maxs.back().is_some_and(|(_, x)| *x <= value) {
+                    self.maxs.pop_back();
+                }
+                self.maxs.push_back((idx, value));
+            }
+            let size = self.size;
+            for deque in [&mut self.mins, &mut self.maxs] {
+                if deque.front().is_some_and(|(x, _)| x + size <= idx) {
+                    deque.pop_front();
+                }
+            }
+
+            if self.values.len() == self.size {
+                let unordered = self
+                    .unordered
+                    .filter(|(x, _)| x + size > idx)
+                    .map(|(_, value)| value);
+                let sum = WindowSum::total(&self.sum);
+                return Some(Window {
+                    sum,
+                    min: unordered.unwrap_or_else(|| self.mins[0].1),
+                    max: unordered.unwrap_or_else(|| self.maxs[0].1),
+                    mean: sum
+                        .to_f64()
+                        .map_or(f64::NAN, |sum| sum / self.size as f64),
+                });
+            }
+        }
+    }
+}
+
+// Panics if the size is 0
+pub fn sliding_windows<I>(
+    iter: impl IntoIterator<IntoIter = I>,
+    size: usize,
+) -> SlidingWindows<I>
+where
+    I: Iterator,
+    I::Item: WindowSum,
+{
+    assert!(size > 0, "Window size must be positive");
+    SlidingWindows {
+        iter: iter.into_iter(),
+        size,
+        idx: 0,
+        values: VecDeque::with_capacity(size),
+        sum: Default::default(),
+        mins: VecDeque::new(),
+        maxs: VecDeque::new(),
+        unordered: None,
+    }
+}
+
+// How consecutive readings compare. Runs are the index ranges of the longest
+// strictly increasing and strictly decreasing stretches of readings.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Trends {
+    pub increases: usize,
+    pub decreases: usize,
+    pub plateaus: usize,
+    pub longest_increase: Range<usize>,
+    pub longest_decrease: Range<usize>,
+}
+
+// Pairs that can't be compared, such as a NaN reading, count as none of
+// increase, decrease or plateau and break any run
+pub fn trends<T: PartialOrd>(iter: impl IntoIterator<Item = T>) -> Trends {
+    let mut trends = Trends::default();
+    let mut prev: Option<T> = None;
+    // Index the current run started at and its direction
+    let mut run: Option<(usize, Ordering)> = None;
+
+    for (idx, value) in iter.into_iter().enumerate() {
+        let ordering = prev.as_ref().and_then(|prev| value.partial_cmp(prev));
+        match ordering {
+            Some(Ordering::Greater) => trends.increases += 1,
+            Some(Ordering::Less) => trends.decreases += 1,
+            Some(Ordering::Equal) => trends.plateaus += 1,
+            None => (),
+        }
+
+        run = match (run, ordering) {
+            (Some((start, curr)), Some(next)) if curr == next => {
+                Some((start, next))
+            }
+            (_, Some(next)) => Some((idx - 1, next)),
+            (_, None) => None,
+        };
+        let longest = match run {
+            Some((_, Ordering::Greater)) => Some(&mut trends.longest_increase),
+            Some((_, Ordering::Less)) => Some(&mut trends.longest_decrease),
+            _ => None,
+        };
+        if let (Some(longest), Some((start, _))) = (longest, run) {
+            if idx + 1 - start > longest.len() {
+                *longest = start..idx + 1;
+            }
+        }
+
+        prev = Some(value);
+    }
+
+    trends
+}
+
+// Iterator adapter, e.g. depths.iter().copied().sliding_windows(3)
+pub trait SlidingWindow: Iterator + Sized {
+    fn sliding_windows(self, size: usize) -> SlidingWindows<Self>
+    where
+        Self::Item: WindowSum,
+    {
+        sliding_windows(self, size)
+    }
+
+    fn trends(self) -> Trends
+    where
+        Self::Item: PartialOrd,
+    {
+        trends(self)
+    }
+}
+
+impl<I: Iterator> SlidingWindow for I {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> Vec<u32> {
+        vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263]
+    }
+
+    #[test]
+    fn test_sliding_windows() {
+        let windows: Vec<Window<u32>> =
+            get_test_input().into_iter().sliding_windows(3).collect();
+        let sums: Vec<u32> = windows.iter().map(|x| x.sum).collect();
+        assert_eq!(sums, vec![607, 618, 618, 617, 647, 716, 769, 792]);
+        let mins: Vec<u32> = windows.iter().map(|x| x.min).collect();
+        assert_eq!(mins, vec![199, 200, 200, 200, 200, 207, 240, 260]);
+        let maxs: Vec<u32> = windows.iter().map(|x| x.max).collect();
+        assert_eq!(maxs, vec![208, 210, 210, 210, 240, 269, 269, 269]);
+        assert_eq!(windows[2].mean, 206.0);
+
+        assert_eq!(sliding_windows(get_test_input(), 11).count(), 0);
+        assert_eq!(sliding_windows(get_test_input(), 1).count(), 10);
+    }
+
+    #[test]
+    fn test_sliding_windows_signed_and_float() {
+        let windows: Vec<Window<i32>> =
+            sliding_windows(vec![-3, 5, -7, 2], 2).collect();
+        let ranges: Vec<(i32, i32)> =
+            windows.iter().map(|x| (x.min, x.max)).collect();
+        assert_eq!(ranges, vec![(-3, 5), (-7, 5), (-7, 2)]);
+        assert_eq!(windows[1].sum, -2);
+
+        let window = sliding_windows(vec![0.5, 1.5, 4.0], 3).next().unwrap();
+        assert_eq!((window.min, window.max, window.mean), (0.5, 4.0, 2.0));
+    }
+
+    #[test]
+    fn test_sliding_windows_non_finite() {
+        let sums = |values: Vec<f64>, size| -> Vec<f64> {
+            sliding_windows(values, size).map(|x| x.sum).collect()
+        };
+
+        // Readings that left the window no longer count
+        let infinity = f64::INFINITY;
+        assert_eq!(sums(vec![infinity, 1.0, 2.0], 1), vec![infinity, 1.0, 2.0]);
+        assert_eq!(
+            sums(vec![1e16, 1.0, 1.0, 1.0], 1),
+            vec![1e16, 1.0, 1.0, 1.0]
+        );
+        assert_eq!(sums(vec![1e16, 1.0, 1.0, 1.0], 2)[2], 2.0);
+
+        let windows: Vec<Window<f64>> =
+            sliding_windows(vec![1.0, f64::NAN, 3.0, 2.0], 2).collect();
+        for window in &windows[..2] {
+            assert!(window.sum.is_nan() && window.mean.is_nan());
+            assert!(window.min.is_nan() && window.max.is_nan());
+        }
+        assert_eq!(
+            (windows[2].min, windows[2].max, windows[2].sum),
+            (2.0, 3.0, 5.0)
+        );
+    }
+
+    #[test]
+    fn test_sliding_windows_running_sums() {
+        // The oldest reading leaves before the next enters, so sums near the
+        // maximum of an unsigned type don't overflow
+        let sums: Vec<u8> = sliding_windows(vec![200u8, 50, 100], 2)
+            .map(|x| x.sum)
+            .collect();
+        assert_eq!(sums, vec![250, 150]);
+
+        let infinity = f64::INFINITY;
+        let sums: Vec<f64> = sliding_windows(vec![infinity, -infinity, 1.0], 2)
+            .map(|x| x.sum)
+            .collect();
+        assert!(sums[0].is_nan());
+        assert_eq!(sums[1], -infinity);
+
+        // Re-summing every window would take ~10^10 additions here
+        let size = 100_000;
+        let values: Vec<f64> = (0..2 * size).map(|x| x as f64 / 8.0).collect();
+        let start = std::time::Instant::now();
+        let last = sliding_windows(values, size).last().unwrap();
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(last.sum, (size..2 * size).sum::<usize>() as f64 / 8.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Window size must be positive")]
+    fn test_sliding_windows_empty() {
+        sliding_windows(vec![1, 2], 0);
+    }
+
+    #[test]
+    fn test_trends() {
+        let result = get_test_input().iter().trends();
+        assert_eq!(
+            (result.increases, result.decreases, result.plateaus),
+            (7, 2, 0)
+        );
+        assert_eq!(result.longest_increase, 0..4);
+        assert_eq!(result.longest_decrease, 3..5);
+
+        let sums = get_test_input()
+            .into_iter()
+            .sliding_windows(3)
+            .map(|x| x.sum);
+        let result = sums.trends();
+        assert_eq!(
+            (result.increases, result.decreases, result.plateaus),
+            (5, 1, 1)
+        );
+
+        assert_eq!(trends::<u32>(vec![]), Trends::default());
+        let result = trends(vec![1.0, 2.0, f64::NAN, 3.0, 4.0, 5.0]);
+        assert_eq!((result.increases, result.decreases), (3, 0));
+        assert_eq!(result.longest_increase, 3..6);
+    }
+}
@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2021::day02::{command_model, Submarine, COMMAND_MODELS};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let command_list: Vec<String> =
+            s.split('\n').map(String::from).collect();
+
+        // Arithmetic is checked, so overflow is reported instead of panicking
+        for name in COMMAND_MODELS {
+            let model = command_model(name).unwrap();
+            let mut sub = Submarine::<i32>::new(0, 0, 0, model);
+            if sub.drive(&command_list).is_ok() {
+                let _ = sub.get_product();
+            }
+        }
+    }
+});
@@ -1,4 +1,5 @@
 use super::text_file_to_vec;
+use common::window::SlidingWindow;
 
 pub fn main() {
     let raw_data = text_file_to_vec("data/day01.txt");
@@ -12,55 +13,21 @@ pub fn main() {
     println!("day01 part2: {}", result);
 }
 
-fn count_depth_increases(sonar_sweep: &Vec<u32>) -> u32 {
-    let mut num_increases: u32 = 0;
-    let mut prev_data_point: Option<u32> = None;
-
-    for data_point in sonar_sweep {
-        match prev_data_point {
-            Some(value) => {
-                if data_point > &value {
-                    num_increases += 1;
-                }
-            }
-            None => (),
-        }
-
-        prev_data_point = Some(data_point.clone());
-    }
-
-    num_increases
+fn count_depth_increases(sonar_sweep: &[u32]) -> usize {
+    sonar_sweep.iter().trends().increases
 }
 
 fn count_depth_increases_window(
-    sonar_sweep: &Vec<u32>,
+    sonar_sweep: &[u32],
     window_size: usize,
-) -> u32 {
-    assert!(window_size <= sonar_sweep.len());
-
-    let mut num_increases: u32 = 0;
-    let mut window: Vec<u32> = Vec::new();
-
-    for data_point in sonar_sweep {
-        if window.len() < window_size {
-            window.push(data_point.clone());
-        } else {
-            let curr_window_sum: u32 = window.iter().sum();
-
-            for i in 0..(window_size - 1) {
-                window[i] = window[i + 1];
-            }
-            window[window_size - 1] = data_point.clone();
-
-            let new_window_sum: u32 = window.iter().sum();
-
-            if new_window_sum > curr_window_sum {
-                num_increases += 1;
-            }
-        }
-    }
-
-    num_increases
+) -> usize {
+    sonar_sweep
+        .iter()
+        .copied()
+        .sliding_windows(window_size)
+        .map(|window| window.sum)
+        .trends()
+        .increases
 }
 
 #[cfg(test)]
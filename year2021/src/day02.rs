@@ -1,24 +1,34 @@
 use super::text_file_to_vec;
-
+use common::geometry::Point2;
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub};
+use std::fmt;
 use std::str::FromStr;
+use trajectory::{State, Trajectory};
+
+pub mod planner;
+pub mod script;
+pub mod trajectory;
 
 pub fn main() {
     let raw_data = text_file_to_vec("data/day02.txt");
 
-    let mut sub1 = Submarine::new(0, 0, 0, true);
-    sub1.drive(&raw_data);
-    println!("day02 part1: {}", sub1.get_product());
+    let mut sub1 = Submarine::new(0, 0, 0, Box::new(Direct));
+    sub1.drive(&raw_data).expect("Invalid command list.");
+    let product = sub1.get_product().expect("Product overflowed.");
+    println!("day02 part1: {}", product);
 
-    let mut sub2 = Submarine::new(0, 0, 0, false);
-    sub2.drive(&raw_data);
-    println!("day02 part2: {}", sub2.get_product());
+    let mut sub2 = Submarine::new(0, 0, 0, Box::new(Aim));
+    sub2.drive(&raw_data).expect("Invalid command list.");
+    let product = sub2.get_product().expect("Product overflowed.");
+    println!("day02 part2: {}", product);
 }
 
-#[derive(PartialEq)]
-enum Motion {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Motion {
     Up,
     Down,
     Forward,
+    Back,
 }
 
 impl FromStr for Motion {
@@ -29,85 +39,238 @@ impl FromStr for Motion {
             "up" => Ok(Motion::Up),
             "down" => Ok(Motion::Down),
             "forward" => Ok(Motion::Forward),
+            "back" => Ok(Motion::Back),
             _ => Err(()),
         }
     }
 }
 
-struct Submarine<T> {
-    depth: T,
-    position: T,
-    aim: T,
-    apply_command: fn(&mut Submarine<T>, Motion, T),
+impl fmt::Display for Motion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Motion::Up => "up",
+            Motion::Down => "down",
+            Motion::Forward => "forward",
+            Motion::Back => "back",
+        };
+        write!(f, "{}", name)
+    }
 }
 
-impl<T> Submarine<T>
+#[derive(Debug, PartialEq)]
+pub enum DriveError {
+    InvalidCommand { index: usize },
+    // The command model could not apply the command, e.g. it overflowed or
+    // the model doesn't allow the move
+    Rejected { index: usize },
+}
+
+// Movement rules for a submarine. A command that cannot be applied returns
+// None, and the submarine then discards any change made to the location and
+// aim.
+pub trait CommandModel<T> {
+    fn apply(
+        &self,
+        location: &mut Point2<T>,
+        aim: &mut T,
+        motion: Motion,
+        value: T,
+    ) -> Option<()>;
+
+    // False if no commands at all can take the submarine from one state to
+    // the other, e.g. because the model never changes the aim. The default
+    // knows of no such pairs.
+    fn can_reach(&self, _from: &State<T>, _to: &State<T>) -> bool {
+        true
+    }
+}
+
+// Part 1: up and down change the depth directly
+pub struct Direct;
+
+impl<T> CommandModel<T> for Direct
 where
-    T: Copy
-        + std::ops::Add<Output = T>
-        + std::ops::Sub<Output = T>
-        + std::ops::Mul<Output = T>,
+    T: CheckedAdd + CheckedSub + PartialEq,
 {
-    fn new(depth: T, position: T, aim: T, part1: bool) -> Self {
-        let apply_command = if part1 {
-            Self::apply_command_part1
-        } else {
-            Self::apply_command_part2
+    fn apply(
+        &self,
+        location: &mut Point2<T>,
+        _aim: &mut T,
+        motion: Motion,
+        value: T,
+    ) -> Option<()> {
+        match motion {
+            Motion::Up => location.y = location.y.checked_sub(&value)?,
+            Motion::Down => location.y = location.y.checked_add(&value)?,
+            Motion::Forward => location.x = location.x.checked_add(&value)?,
+            Motion::Back => location.x = location.x.checked_sub(&value)?,
         };
 
-        Submarine {
-            depth,
-            position,
-            aim,
-            apply_command,
-        }
+        Some(())
     }
 
-    fn apply_command_part1(&mut self, motion: Motion, value: T) {
-        match motion {
-            Motion::Up => self.depth = self.depth - value,
-            Motion::Down => self.depth = self.depth + value,
-            Motion::Forward => self.position = self.position + value,
-        };
+    fn can_reach(&self, from: &State<T>, to: &State<T>) -> bool {
+        from.aim == to.aim
     }
+}
 
-    fn apply_command_part2(&mut self, motion: Motion, value: T) {
+// Part 2: up and down change the aim, forward dives along it and back
+// retraces it
+pub struct Aim;
+
+impl<T> CommandModel<T> for Aim
+where
+    T: CheckedAdd + CheckedSub + CheckedMul,
+{
+    fn apply(
+        &self,
+        location: &mut Point2<T>,
+        aim: &mut T,
+        motion: Motion,
+        value: T,
+    ) -> Option<()> {
         match motion {
-            Motion::Up => self.aim = self.aim - value,
-            Motion::Down => self.aim = self.aim + value,
+            Motion::Up => *aim = aim.checked_sub(&value)?,
+            Motion::Down => *aim = aim.checked_add(&value)?,
             Motion::Forward => {
-                self.position = self.position + value;
-                self.depth = self.depth + self.aim * value;
+                let x = location.x.checked_add(&value)?;
+                let dy = aim.checked_mul(&value)?;
+                let y = location.y.checked_add(&dy)?;
+                *location = Point2::new(x, y);
             }
+            Motion::Back => {
+                let x = location.x.checked_sub(&value)?;
+                let dy = aim.checked_mul(&value)?;
+                let y = location.y.checked_sub(&dy)?;
+                *location = Point2::new(x, y);
+            }
+        }
+
+        Some(())
+    }
+}
+
+pub const COMMAND_MODELS: [&str; 2] = ["direct", "aim"];
+
+pub fn command_model<T>(name: &str) -> Option<Box<dyn CommandModel<T>>>
+where
+    T: CheckedAdd + CheckedSub + CheckedMul + PartialEq,
+{
+    match name {
+        "direct" => Some(Box::new(Direct)),
+        "aim" => Some(Box::new(Aim)),
+        _ => None,
+    }
+}
+
+// The x coordinate is the horizontal position, y is the depth. All arithmetic
+// is checked, so T can be a fixed width integer or a big integer.
+pub struct Submarine<T> {
+    location: Point2<T>,
+    aim: T,
+    model: Box<dyn CommandModel<T>>,
+    trajectory: Option<Trajectory<T>>,
+}
+
+impl<T> Submarine<T>
+where
+    T: Clone + CheckedMul,
+{
+    pub fn new(
+        depth: T,
+        position: T,
+        aim: T,
+        model: Box<dyn CommandModel<T>>,
+    ) -> Self {
+        Submarine {
+            location: Point2::new(position, depth),
+            aim,
+            model,
+            trajectory: None,
+        }
+    }
+
+    // Record the current state and the state after every later command
+    pub fn record(&mut self) {
+        self.trajectory = Some(Trajectory { states: Vec::new() });
+        self.record_state();
+    }
+
+    // None unless recording was turned on
+    pub fn trajectory(&self) -> Option<&Trajectory<T>> {
+        self.trajectory.as_ref()
+    }
+
+    pub fn state(&self) -> State<T> {
+        State {
+            location: self.location.clone(),
+            aim: self.aim.clone(),
+        }
+    }
+
+    fn record_state(&mut self) {
+        if let Some(trajectory) = &mut self.trajectory {
+            trajectory.states.push(State {
+                location: self.location.clone(),
+                aim: self.aim.clone(),
+            });
         }
     }
 
-    fn drive(&mut self, command_list: &Vec<String>)
+    pub fn drive(&mut self, command_list: &[String]) -> Result<(), DriveError>
     where
         T: std::str::FromStr,
-        <T as std::str::FromStr>::Err: std::fmt::Debug,
     {
-        for command in command_list {
-            // Parse the command string
-            let mut iter = command.split_whitespace();
-            let motion = Motion::from_str(iter.next().unwrap()).unwrap();
-            let value: T = iter.next().unwrap().parse().unwrap();
+        for (index, command) in command_list.iter().enumerate() {
+            let (motion, value) = Self::parse_command(command)
+                .ok_or(DriveError::InvalidCommand { index })?;
 
-            (self.apply_command)(self, motion, value);
+            self.apply(motion, value)
+                .ok_or(DriveError::Rejected { index })?;
         }
+
+        Ok(())
     }
 
-    pub fn get_product(&self) -> T {
-        self.depth * self.position
+    // Apply a single command with the submarine's command model, leaving
+    // the submarine as it was if the model rejects it
+    pub fn apply(&mut self, motion: Motion, value: T) -> Option<()> {
+        let mut location = self.location.clone();
+        let mut aim = self.aim.clone();
+        self.model.apply(&mut location, &mut aim, motion, value)?;
+        self.location = location;
+        self.aim = aim;
+        self.record_state();
+        Some(())
+    }
+
+    fn parse_command(command: &str) -> Option<(Motion, T)>
+    where
+        T: std::str::FromStr,
+    {
+        // Commands are "{motion} {value}"
+        match command.split_whitespace().collect::<Vec<&str>>()[..] {
+            [motion, value] => {
+                Some((Motion::from_str(motion).ok()?, value.parse().ok()?))
+            }
+            _ => None,
+        }
+    }
+
+    // None if the product overflows
+    pub fn get_product(&self) -> Option<T> {
+        self.location.x.checked_mul(&self.location.y)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use num_bigint::BigInt;
+    use num_traits::Zero;
 
     fn get_test_input() -> Vec<String> {
-        vec![
+        [
             "forward 5",
             "down 5",
             "forward 8",
@@ -122,15 +285,151 @@ mod test {
 
     #[test]
     fn test_submarine_part1() {
-        let mut sub = Submarine::new(0, 0, 0, true);
-        sub.drive(&get_test_input());
-        assert_eq!(sub.get_product(), 150);
+        let mut sub = Submarine::new(0, 0, 0, Box::new(Direct));
+        sub.drive(&get_test_input()).unwrap();
+        assert_eq!(sub.get_product(), Some(150));
     }
 
     #[test]
     fn test_submarine_part2() {
-        let mut sub = Submarine::new(0, 0, 0, false);
-        sub.drive(&get_test_input());
-        assert_eq!(sub.get_product(), 900);
+        let mut sub = Submarine::new(0, 0, 0, Box::new(Aim));
+        sub.drive(&get_test_input()).unwrap();
+        assert_eq!(sub.get_product(), Some(900));
+    }
+
+    #[test]
+    fn test_submarine_invalid_command() {
+        for command in ["", "forward", "sideways 5", "up 5 5", "down x"] {
+            let mut sub = Submarine::new(0, 0, 0, Box::new(Direct));
+            let command_list = vec!["down 1".to_string(), command.to_string()];
+            assert_eq!(
+                sub.drive(&command_list),
+                Err(DriveError::InvalidCommand { index: 1 })
+            );
+        }
+    }
+
+    #[test]
+    fn test_submarine_overflow() {
+        let command_list: Vec<String> =
+            ["down 100", "forward 200", "forward 200"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect();
+
+        let mut sub = Submarine::<i16>::new(0, 0, 0, Box::new(Aim));
+        assert_eq!(
+            sub.drive(&command_list),
+            Err(DriveError::Rejected { index: 2 })
+        );
+
+        let mut sub = Submarine::<i32>::new(0, 0, 0, Box::new(Direct));
+        sub.drive(&["down 65536".to_string(), "forward 65536".to_string()])
+            .unwrap();
+        assert_eq!(sub.get_product(), None);
+    }
+
+    #[test]
+    fn test_submarine_big_integer() {
+        let command_list: Vec<String> = [
+            "down 4000000000",
+            "forward 4000000000",
+            "forward 4000000000",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+
+        let zero = BigInt::zero();
+        let mut sub =
+            Submarine::new(zero.clone(), zero.clone(), zero, Box::new(Aim));
+        sub.drive(&command_list).unwrap();
+        let expected: BigInt =
+            "256000000000000000000000000000".parse().unwrap();
+        assert_eq!(sub.get_product(), Some(expected));
+    }
+
+    // Aim rule that never dives deeper than a fixed depth
+    struct MaxDepth(i32);
+
+    impl CommandModel<i32> for MaxDepth {
+        fn apply(
+            &self,
+            location: &mut Point2<i32>,
+            aim: &mut i32,
+            motion: Motion,
+            value: i32,
+        ) -> Option<()> {
+            Aim.apply(location, aim, motion, value)?;
+            location.y = location.y.min(self.0);
+            Some(())
+        }
+    }
+
+    #[test]
+    fn test_submarine_custom_model() {
+        let mut sub = Submarine::new(0, 0, 0, Box::new(MaxDepth(50)));
+        sub.drive(&get_test_input()).unwrap();
+        assert_eq!(sub.get_product(), Some(750));
+    }
+
+    // Aim rule that refuses to dive deeper than a fixed depth, after already
+    // having moved
+    struct Floor(i32);
+
+    impl CommandModel<i32> for Floor {
+        fn apply(
+            &self,
+            location: &mut Point2<i32>,
+            aim: &mut i32,
+            motion: Motion,
+            value: i32,
+        ) -> Option<()> {
+            Aim.apply(location, aim, motion, value)?;
+            (location.y <= self.0).then_some(())
+        }
+    }
+
+    #[test]
+    fn test_submarine_rejected_command() {
+        let mut sub = Submarine::new(0, 0, 0, Box::new(Floor(50)));
+        sub.record();
+        assert_eq!(
+            sub.drive(&get_test_input()),
+            Err(DriveError::Rejected { index: 5 })
+        );
+
+        // Still where the fifth command left it
+        let state = sub.state();
+        assert_eq!((state.location, state.aim), (Point2::new(13, 40), 10));
+        assert_eq!(sub.trajectory().unwrap().states.len(), 6);
+    }
+
+    #[test]
+    fn test_command_model() {
+        for (name, expected) in COMMAND_MODELS.iter().zip([150, 900]) {
+            let model = command_model(name).unwrap();
+            let mut sub = Submarine::new(0, 0, 0, model);
+            sub.drive(&get_test_input()).unwrap();
+            assert_eq!(sub.get_product(), Some(expected));
+        }
+        assert!(command_model::<i32>("drag").is_none());
+    }
+
+    // Crash reproducers found by the submarine_drive fuzz target
+    #[test]
+    fn test_submarine_fuzz_regressions() {
+        let mut sub = Submarine::<i32>::new(0, 0, 0, Box::new(Direct));
+        assert_eq!(
+            sub.drive(&["forward".to_string()]),
+            Err(DriveError::InvalidCommand { index: 0 })
+        );
+
+        let command_list = ["down 2147483647", "forward 2"].map(String::from);
+        let mut sub = Submarine::<i32>::new(0, 0, 0, Box::new(Aim));
+        assert_eq!(
+            sub.drive(&command_list),
+            Err(DriveError::Rejected { index: 1 })
+        );
     }
 }
@@ -0,0 +1,147 @@
+use super::trajectory::State;
+use super::{Motion, Submarine};
+use common::search::bfs;
+use num_traits::{CheckedMul, FromPrimitive};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+const MOTIONS: [Motion; 4] =
+    [Motion::Up, Motion::Down, Motion::Forward, Motion::Back];
+
+#[derive(Debug, PartialEq)]
+pub enum PlanError {
+    // The command model can never reach the target, whatever the commands
+    Impossible,
+    // No command list within the length and value limits reaches the target
+    NotWithinBounds,
+}
+
+// Shortest command list taking the submarine from its current state to the
+// target under its command model, using at most max_len commands with values
+// from 1 to max_value. The search is breadth first over every command, so
+// keep both limits small.
+pub fn plan<T>(
+    sub: &Submarine<T>,
+    target: &State<T>,
+    max_len: usize,
+    max_value: u32,
+) -> Result<Vec<String>, PlanError>
+where
+    T: Clone + Eq + Hash + Display + CheckedMul + FromPrimitive,
+{
+    if !sub.model.can_reach(&sub.state(), target) {
+        return Err(PlanError::Impossible);
+    }
+
+    let values: Vec<T> = (1..=max_value).filter_map(T::from_u32).collect();
+    let commands: Vec<(Motion, T)> = MOTIONS
+        .iter()
+        .flat_map(|&motion| values.iter().map(move |x| (motion, x.clone())))
+        .collect();
+
+    let apply = |state: &State<T>, (motion, value): &(Motion, T)| {
+        let mut next = state.clone();
+        sub.model.apply(
+            &mut next.location,
+            &mut next.aim,
+            *motion,
+            value.clone(),
+        )?;
+        Some(next)
+    };
+
+    // Breadth first, so the first time a state is seen is also its fewest
+    // commands. States at max_len commands aren't expanded.
+    let mut lens = HashMap::from([(sub.state(), 0)]);
+    let path = bfs(
+        sub.state(),
+        |state| {
+            let len = lens[state];
+            let mut next = Vec::new();
+            if len < max_len {
+                for command in &commands {
+                    if let Some(state) = apply(state, command) {
+                        lens.entry(state.clone()).or_insert(len + 1);
+                        next.push(state);
+                    }
+                }
+            }
+            next
+        },
+        |state| state == target,
+    )
+    .ok_or(PlanError::NotWithinBounds)?;
+
+    // Recover the command taken between each pair of consecutive states
+    let command_list = path
+        .windows(2)
+        .map(|pair| {
+            let (motion, value) = commands
+                .iter()
+                .find(|command| {
+                    apply(&pair[0], command).as_ref() == Some(&pair[1])
+                })
+                .unwrap();
+            format!("{} {}", motion, value)
+        })
+        .collect();
+
+    Ok(command_list)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{Aim, Direct};
+    use super::*;
+    use common::geometry::Point2;
+
+    fn target(depth: i32, position: i32, aim: i32) -> State<i32> {
+        State {
+            location: Point2::new(position, depth),
+            aim,
+        }
+    }
+
+    #[test]
+    fn test_plan_direct() {
+        let sub = Submarine::new(0, 0, 0, Box::new(Direct));
+        let command_list = plan(&sub, &target(5, -3, 0), 4, 5).unwrap();
+        assert_eq!(command_list, vec!["down 5", "back 3"]);
+
+        // Needs two commands for the depth with values of at most 3
+        let command_list = plan(&sub, &target(5, 0, 0), 4, 3).unwrap();
+        assert_eq!(command_list.len(), 2);
+
+        // Direct commands never change the aim, so there's no need to search
+        assert_eq!(
+            plan(&sub, &target(0, 0, 1), 100, 1000),
+            Err(PlanError::Impossible)
+        );
+        assert_eq!(plan(&sub, &target(0, 0, 0), 3, 3), Ok(vec![]));
+        assert_eq!(
+            plan(&sub, &target(7, 0, 0), 2, 3),
+            Err(PlanError::NotWithinBounds)
+        );
+    }
+
+    #[test]
+    fn test_plan_aim() {
+        let mut sub = Submarine::new(0, 0, 0, Box::new(Aim));
+        let goal = target(12, 6, 3);
+        let command_list = plan(&sub, &goal, 4, 6).unwrap();
+        assert_eq!(command_list, vec!["down 2", "forward 6", "down 1"]);
+
+        sub.drive(&command_list).unwrap();
+        assert_eq!(sub.state(), goal);
+
+        // Depth only changes when moving, which takes at least three commands
+        // to end up back at the same position
+        let sub = Submarine::new(0, 0, 0, Box::new(Aim));
+        assert_eq!(
+            plan(&sub, &target(1, 0, 0), 2, 3),
+            Err(PlanError::NotWithinBounds)
+        );
+        assert_eq!(plan(&sub, &target(1, 0, 0), 4, 3).unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,411 @@
+use super::{Motion, Submarine};
+use common::parse::{
+    alt, delimited, fail, literal, map, map_opt, pair, parse, preceded, signed,
+    spaces, spaces1, terminated, unsigned, ParseError, ParseResult, Parser,
+};
+use num_traits::CheckedMul;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// Command scripts for the submarine. Besides plain "{motion} {value}"
+// commands a script can hold comments, variables, macros and repeated blocks:
+//
+//     # Zig-zag down the trench
+//     let step = 5
+//     macro zig {
+//         down step
+//         forward 10
+//     }
+//     repeat 3 {
+//         zig
+//         back 2  # drift
+//     }
+//
+// A statement ends at a newline, a comment or a closing brace. Variables and
+// macros are visible from the point they are defined onwards.
+
+const KEYWORDS: [&str; 3] = ["let", "macro", "repeat"];
+
+// Nested macro calls allowed before a script is assumed to recurse forever
+const MAX_CALL_DEPTH: usize = 64;
+
+// Blocks allowed inside one another, so parsing can't overflow the stack
+const MAX_NESTING_DEPTH: usize = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<T> {
+    Number(T),
+    Variable(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Statement<T> {
+    Command {
+        motion: Motion,
+        value: Value<T>,
+    },
+    Let {
+        name: String,
+        value: Value<T>,
+    },
+    Macro {
+        name: String,
+        body: Vec<Statement<T>>,
+    },
+    Call {
+        name: String,
+    },
+    Repeat {
+        count: usize,
+        body: Vec<Statement<T>>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Script<T> {
+    pub statements: Vec<Statement<T>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ScriptError {
+    UndefinedVariable { name: String },
+    UndefinedMacro { name: String },
+    RecursionLimit { name: String },
+    // Index of the failing command among all the commands run so far
+    Rejected { index: usize },
+}
+
+impl<T: FromStr> FromStr for Script<T> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let statements = parse(|input| statements(input, 0), input)?;
+        Ok(Script { statements })
+    }
+}
+
+impl<T> Script<T>
+where
+    T: Clone + CheckedMul,
+{
+    pub fn run(&self, sub: &mut Submarine<T>) -> Result<(), ScriptError> {
+        let mut interpreter = Interpreter {
+            variables: HashMap::new(),
+            macros: HashMap::new(),
+            depth: 0,
+            index: 0,
+        };
+        interpreter.run(&self.statements, sub)
+    }
+}
+
+struct Interpreter<'s, T> {
+    variables: HashMap<&'s str, T>,
+    macros: HashMap<&'s str, &'s [Statement<T>]>,
+    depth: usize,
+    index: usize,
+}
+
+impl<'s, T> Interpreter<'s, T>
+where
+    T: Clone + CheckedMul,
+{
+    fn run(
+        &mut self,
+        statements: &'s [Statement<T>],
+        sub: &mut Submarine<T>,
+    ) -> Result<(), ScriptError> {
+        for statement in statements {
+            match statement {
+                Statement::Command { motion, value } => {
+                    let value = self.evaluate(value)?;
+                    sub.apply(*motion, value)
+                        .ok_or(ScriptError::Rejected { index: self.index })?;
+                    self.index += 1;
+                }
+                Statement::Let { name, value } => {
+                    let value = self.evaluate(value)?;
+                    self.variables.insert(name, value);
+                }
+                Statement::Macro { name, body } => {
+                    self.macros.insert(name, body);
+                }
+                Statement::Call { name } => {
+                    let body = self.macros.get(name.as_str()).copied().ok_or(
+                        ScriptError::UndefinedMacro { name: name.clone() },
+                    )?;
+                    if self.depth == MAX_CALL_DEPTH {
+                        return Err(ScriptError::RecursionLimit {
+                            name: name.clone(),
+                        });
+                    }
+                    self.depth += 1;
+                    self.run(body, sub)?;
+                    self.depth -= 1;
+                }
+                Statement::Repeat { count, body } => {
+                    for _ in 0..*count {
+                        self.run(body, sub)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self, value: &Value<T>) -> Result<T, ScriptError> {
+        match value {
+            Value::Number(value) => Ok(value.clone()),
+            Value::Variable(name) => {
+                self.variables.get(name.as_str()).cloned().ok_or(
+                    ScriptError::UndefinedVariable { name: name.clone() },
+                )
+            }
+        }
+    }
+}
+
+// Depth is the number of blocks the statements are nested in
+fn statements<T: FromStr>(
+    mut input: &str,
+    depth: usize,
+) -> ParseResult<'_, Vec<Statement<T>>> {
+    let mut statements = Vec::new();
+    loop {
+        input = skip_blank(input);
+        if input.is_empty() || input.starts_with('}') {
+            return Ok((statements, input));
+        }
+        let (statement, rest) = statement(input, depth)?;
+        let (_, rest) = end_of_statement(rest)?;
+        statements.push(statement);
+        input = rest;
+    }
+}
+
+fn statement<T: FromStr>(
+    input: &str,
+    depth: usize,
+) -> ParseResult<'_, Statement<T>> {
+    let block = |input| block(input, depth);
+    let let_ = map(
+        pair(
+            preceded(keyword("let"), name),
+            preceded(delimited(spaces(), literal("="), spaces()), value),
+        ),
+        |(name, value)| Statement::Let { name, value },
+    );
+    let macro_ = map(
+        pair(preceded(keyword("macro"), name), preceded(spaces(), block)),
+        |(name, body)| Statement::Macro { name, body },
+    );
+    let repeat = map(
+        pair(
+            preceded(keyword("repeat"), unsigned()),
+            preceded(spaces(), block),
+        ),
+        |(count, body)| Statement::Repeat { count, body },
+    );
+    let command = map(
+        pair(
+            map_opt(word, |word| Motion::from_str(word).ok(), "motion"),
+            preceded(spaces1(), value),
+        ),
+        |(motion, value)| Statement::Command { motion, value },
+    );
+    let call = map(name, |name| Statement::Call { name });
+
+    alt(let_, alt(macro_, alt(repeat, alt(command, call))))(input)
+}
+
+fn block<T: FromStr>(
+    input: &str,
+    depth: usize,
+) -> ParseResult<'_, Vec<Statement<T>>> {
+    if depth == MAX_NESTING_DEPTH {
+        let expected = format!("at most {} nested blocks", MAX_NESTING_DEPTH);
+        return fail(input, expected);
+    }
+    let statements = |input| statements(input, depth + 1);
+    delimited(literal("{"), statements, literal("}"))(input)
+}
+
+fn value<T: FromStr>(input: &str) -> ParseResult<'_, Value<T>> {
+    alt(map(signed(), Value::Number), map(name, Value::Variable))(input)
+}
+
+fn keyword<'a>(keyword: &'static str) -> impl Parser<'a, &'a str> {
+    terminated(literal(keyword), spaces1())
+}
+
+// A variable or macro name, which can't be a keyword or a motion
+fn name(input: &str) -> ParseResult<'_, String> {
+    map_opt(
+        word,
+        |word| (!is_reserved(word)).then(|| word.to_string()),
+        "name",
+    )(input)
+}
+
+fn is_reserved(word: &str) -> bool {
+    KEYWORDS.contains(&word) || Motion::from_str(word).is_ok()
+}
+
+fn word(input: &str) -> ParseResult<'_, &str> {
+    let rest = input
+        .trim_start_matches(|c: char| c.is_ascii_alphanumeric() || c == '_');
+    let len = input.len() - rest.len();
+    if len == 0 || input.starts_with(|c: char| c.is_ascii_digit()) {
+        return fail(input, "name");
+    }
+    Ok((&input[..len], rest))
+}
+
+fn end_of_statement(input: &str) -> ParseResult<'_, ()> {
+    let rest = input.trim_start_matches([' ', '\t']);
+    if rest.is_empty() || rest.starts_with(['\n', '#', '}']) {
+        Ok(((), rest))
+    } else {
+        fail(rest, "end of statement")
+    }
+}
+
+// Whitespace, including newlines, and comments
+fn skip_blank(mut input: &str) -> &str {
+    loop {
+        input = input.trim_start();
+        match input.strip_prefix('#') {
+            Some(comment) => {
+                input = comment
+                    .split_once('\n')
+                    .map_or(&comment[comment.len()..], |(_, rest)| rest)
+            }
+            None => return input,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{Aim, Direct};
+    use super::*;
+
+    fn get_test_input() -> &'static str {
+        "# Zig-zag down the trench\n\
+         let step = 5\n\
+         macro zig {\n\
+         \x20   down step\n\
+         \x20   forward 10\n\
+         }\n\
+         repeat 3 {\n\
+         \x20   zig\n\
+         \x20   back 2  # drift\n\
+         }\n"
+    }
+
+    fn run(script: &str, model: &str) -> Result<Option<i32>, ScriptError> {
+        let script: Script<i32> = script.parse().unwrap();
+        let model = super::super::command_model(model).unwrap();
+        let mut sub = Submarine::new(0, 0, 0, model);
+        script.run(&mut sub)?;
+        Ok(sub.get_product())
+    }
+
+    #[test]
+    fn test_parse_script() {
+        let script: Script<i32> =
+            "let n = -2\nrepeat 2 { up n }\n\nfoo".parse().unwrap();
+        assert_eq!(
+            script.statements,
+            vec![
+                Statement::Let {
+                    name: "n".to_string(),
+                    value: Value::Number(-2)
+                },
+                Statement::Repeat {
+                    count: 2,
+                    body: vec![Statement::Command {
+                        motion: Motion::Up,
+                        value: Value::Variable("n".to_string())
+                    }]
+                },
+                Statement::Call {
+                    name: "foo".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_script_errors() {
+        for (script, line, column) in [
+            ("forward 5 5", 1, 11),
+            ("down 1\nsideways 2", 2, 10),
+            ("repeat 2 {\n  up 1\n", 3, 1),
+            ("let forward = 1", 1, 5),
+            ("down 1 }", 1, 8),
+            ("repeat 2 {\n# c", 2, 4),
+        ] {
+            let err = script.parse::<Script<i32>>().unwrap_err();
+            assert_eq!((err.line, err.column), (line, column), "{}", script);
+        }
+
+        let err = "repeat 2 {\n# c".parse::<Script<i32>>().unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 4: expected \"}\"");
+
+        // Fails at the first block nested too deep rather than overflowing
+        let script = "repeat 1 {".repeat(200000);
+        let err = script.parse::<Script<i32>>().unwrap_err();
+        assert_eq!(err.column, 10 * MAX_NESTING_DEPTH + 10);
+        assert_eq!(err.expected, "at most 64 nested blocks");
+        let script = "repeat 1 { ".repeat(64) + &"}".repeat(64);
+        assert!(script.parse::<Script<i32>>().is_ok());
+    }
+
+    #[test]
+    fn test_run_script() {
+        // Three times: depth += 5, position += 10, position -= 2
+        assert_eq!(run(get_test_input(), "direct"), Ok(Some(24 * 15)));
+        // Aim 5, 10, 15 in turn: depth += 10 * aim - 2 * aim
+        assert_eq!(run(get_test_input(), "aim"), Ok(Some(24 * 240)));
+
+        // Plain command lists are scripts too
+        let commands = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2";
+        assert_eq!(run(commands, "aim"), Ok(Some(900)));
+    }
+
+    #[test]
+    fn test_run_script_errors() {
+        assert_eq!(
+            run("down 1\nup depth", "direct"),
+            Err(ScriptError::UndefinedVariable {
+                name: "depth".to_string()
+            })
+        );
+        assert_eq!(
+            run("zig\nmacro zig { down 1 }", "direct"),
+            Err(ScriptError::UndefinedMacro {
+                name: "zig".to_string()
+            })
+        );
+        assert_eq!(
+            run("macro loop { down 1\n loop }\nloop", "direct"),
+            Err(ScriptError::RecursionLimit {
+                name: "loop".to_string()
+            })
+        );
+
+        let script: Script<i8> = "repeat 10 { forward 20 }".parse().unwrap();
+        let mut sub = Submarine::new(0, 0, 0, Box::new(Direct));
+        assert_eq!(
+            script.run(&mut sub),
+            Err(ScriptError::Rejected { index: 6 })
+        );
+        let mut sub = Submarine::new(0, 0, 0, Box::new(Aim));
+        assert_eq!(
+            script.run(&mut sub),
+            Err(ScriptError::Rejected { index: 6 })
+        );
+    }
+}
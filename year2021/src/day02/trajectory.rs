@@ -0,0 +1,207 @@
+use common::geometry::Point2;
+use num_traits::ToPrimitive;
+use std::fmt::{Display, Write};
+
+const SVG_WIDTH: f64 = 800.0;
+const SVG_HEIGHT: f64 = 400.0;
+const SVG_MARGIN: f64 = 40.0;
+const SVG_COLORS: [&str; 4] = ["#1f77b4", "#d62728", "#2ca02c", "#ff7f0e"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct State<T> {
+    pub location: Point2<T>,
+    pub aim: T,
+}
+
+// The state a submarine started recording in, followed by its state after
+// every command applied since
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trajectory<T> {
+    pub states: Vec<State<T>>,
+}
+
+// One row per state of each labelled trajectory
+pub fn to_csv<T: Display>(profiles: &[(&str, &Trajectory<T>)]) -> String {
+    let mut csv = String::from("model,step,position,depth,aim\n");
+    for (label, trajectory) in profiles {
+        for (step, state) in trajectory.states.iter().enumerate() {
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                label, step, state.location.x, state.location.y, state.aim
+            )
+            .unwrap();
+        }
+    }
+
+    csv
+}
+
+// Depth (downwards) against horizontal position, one polyline per labelled
+// trajectory, all drawn to the same scale
+pub fn to_svg<T: ToPrimitive>(profiles: &[(&str, &Trajectory<T>)]) -> String {
+    let points: Vec<Vec<(f64, f64)>> = profiles
+        .iter()
+        .map(|(_, trajectory)| {
+            trajectory
+                .states
+                .iter()
+                .filter_map(|state| {
+                    Some((
+                        state.location.x.to_f64()?,
+                        state.location.y.to_f64()?,
+                    ))
+                })
+                .collect()
+        })
+        .collect();
+
+    let all = points.iter().flatten();
+    let (min_x, max_x) = bounds(all.clone().map(|(x, _)| *x));
+    let (min_y, max_y) = bounds(all.map(|(_, y)| *y));
+    let scale = |value: f64, min: f64, max: f64, size: f64| {
+        SVG_MARGIN + (value - min) / (max - min) * (size - 2.0 * SVG_MARGIN)
+    };
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" \
+         height=\"{}\" viewBox=\"0 0 {} {}\">",
+        SVG_WIDTH, SVG_HEIGHT, SVG_WIDTH, SVG_HEIGHT
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n\
+         <text x=\"{}\" y=\"{}\" font-size=\"12\">position {} to {}, \
+         depth {} to {}</text>",
+        SVG_MARGIN,
+        SVG_HEIGHT - SVG_MARGIN / 2.0,
+        min_x,
+        max_x,
+        min_y,
+        max_y
+    )
+    .unwrap();
+
+    for (idx, ((label, _), points)) in profiles.iter().zip(&points).enumerate()
+    {
+        let color = SVG_COLORS[idx % SVG_COLORS.len()];
+        let coords: Vec<String> = points
+            .iter()
+            .map(|(x, y)| {
+                format!(
+                    "{:.1},{:.1}",
+                    scale(*x, min_x, max_x, SVG_WIDTH),
+                    scale(*y, min_y, max_y, SVG_HEIGHT)
+                )
+            })
+            .collect();
+        writeln!(
+            svg,
+            "<polyline fill=\"none\" stroke=\"{}\" points=\"{}\"/>\n\
+             <text x=\"{}\" y=\"{}\" font-size=\"12\" fill=\"{}\">{}</text>",
+            color,
+            coords.join(" "),
+            SVG_MARGIN,
+            SVG_MARGIN / 2.0 + 14.0 * idx as f64,
+            color,
+            label
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+// Minimum and maximum, widened so a flat trajectory still has a range. No
+// values give 0 to 1.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values.fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min, max): (f64, f64), x| (min.min(x), max.max(x)),
+    );
+    if min > max {
+        (0.0, 1.0)
+    } else if min == max {
+        (min, min + 1.0)
+    } else {
+        (min, max)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{Aim, Direct, Submarine};
+    use super::*;
+
+    fn get_test_input() -> Vec<String> {
+        ["forward 5", "down 5", "forward 8", "up 3"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_bounds() {
+        // Far from the origin, the viewport shouldn't stretch back to it
+        assert_eq!(
+            bounds([100.0, 120.0, 110.0].iter().copied()),
+            (100.0, 120.0)
+        );
+        assert_eq!(bounds([-5.0, -2.0].iter().copied()), (-5.0, -2.0));
+        assert_eq!(bounds([7.0, 7.0].iter().copied()), (7.0, 8.0));
+        assert_eq!(bounds(std::iter::empty()), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_record_trajectory() {
+        let mut sub = Submarine::new(0, 0, 0, Box::new(Aim));
+        assert_eq!(sub.trajectory(), None);
+        sub.record();
+        sub.drive(&get_test_input()).unwrap();
+
+        let trajectory = sub.trajectory().unwrap();
+        let depths: Vec<i32> =
+            trajectory.states.iter().map(|x| x.location.y).collect();
+        assert_eq!(depths, vec![0, 0, 0, 40, 40]);
+        assert_eq!(trajectory.states[4].aim, 2);
+    }
+
+    #[test]
+    fn test_export_trajectory() {
+        let mut direct = Submarine::new(0, 0, 0, Box::new(Direct));
+        let mut aim = Submarine::new(0, 0, 0, Box::new(Aim));
+        for sub in [&mut direct, &mut aim] {
+            sub.record();
+            sub.drive(&get_test_input()[..3]).unwrap();
+        }
+        let profiles = [
+            ("direct", direct.trajectory().unwrap()),
+            ("aim", aim.trajectory().unwrap()),
+        ];
+
+        assert_eq!(
+            to_csv(&profiles),
+            "model,step,position,depth,aim\n\
+             direct,0,0,0,0\n\
+             direct,1,5,0,0\n\
+             direct,2,5,5,0\n\
+             direct,3,13,5,0\n\
+             aim,0,0,0,0\n\
+             aim,1,5,0,0\n\
+             aim,2,5,0,5\n\
+             aim,3,13,40,5\n"
+        );
+
+        let svg = to_svg(&profiles);
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches("<polyline ").count(), 2);
+        // Both start in the top left corner, aim ends bottom right
+        assert!(svg.contains("points=\"40.0,40.0 "));
+        assert!(svg.contains(" 760.0,360.0\"/>"));
+        assert!(svg.contains("position 0 to 13, depth 0 to 40"));
+    }
+}
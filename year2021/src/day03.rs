@@ -1,157 +1,366 @@
 use super::text_file_to_vec;
+use common::consensus::{Consensus, ConsensusError, Keep, Ties};
+use common::grid::{Grid, ParseGridError};
+use num_bigint::BigUint;
+use std::str::FromStr;
 
 pub fn main() {
     let raw_data = text_file_to_vec("data/day03.txt");
+    let diagnostic_report =
+        parse_diagnostic_report(&raw_data).expect("Invalid report.");
 
-    let num_bits = raw_data[0].len();
-    let diagnostic_report = raw_data
-        .iter()
-        .map(|x| u32::from_str_radix(x, 2).unwrap())
-        .collect();
+    let result =
+        get_power_consumption(&diagnostic_report, TiePolicy::PreferOne)
+            .expect("Instructions unclear.");
+    println!("day03 part1: {}", result);
 
-    println!(
-        "day03 part1: {}",
-        get_power_consumption(&diagnostic_report, num_bits)
-    );
-    println!(
-        "day03 part2: {}",
-        get_life_support(&diagnostic_report, num_bits)
-    );
+    let result = get_life_support(&diagnostic_report, TiePolicy::PreferOne)
+        .expect("Instructions unclear.");
+    println!("day03 part2: {}", result);
 }
 
-fn get_power_consumption(diagnostic_report: &Vec<u32>, num_bits: usize) -> u32 {
-    let (num_zeros, num_ones) =
-        count_zeros_and_ones(&diagnostic_report, num_bits);
-
-    // Compute gamma and epsilon
-    let mut gamma_rate = 0;
-    let mut epsilon_rate = 0;
-    let mut bitpos = 0;
-    while bitpos < num_bits {
-        if num_ones[bitpos] > num_zeros[bitpos] {
-            gamma_rate |= 1 << bitpos;
-        } else if num_ones[bitpos] < num_zeros[bitpos] {
-            epsilon_rate |= 1 << bitpos;
-        } else {
-            panic!("Instructions unclear.")
-        }
+// What counts as the most common value of a bit set in exactly half of the
+// readings. The least common value is always its complement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TiePolicy {
+    PreferZero,
+    PreferOne,
+    Error,
+    // Leave the bit out of gamma and epsilon, or don't filter on it
+    SkipBit,
+}
 
-        bitpos += 1;
-    }
+pub const TIE_POLICIES: [&str; 4] =
+    ["prefer-0", "prefer-1", "error", "skip-bit"];
 
-    gamma_rate * epsilon_rate
+impl FromStr for TiePolicy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "prefer-0" => Ok(TiePolicy::PreferZero),
+            "prefer-1" => Ok(TiePolicy::PreferOne),
+            "error" => Ok(TiePolicy::Error),
+            "skip-bit" => Ok(TiePolicy::SkipBit),
+            _ => Err(()),
+        }
+    }
 }
 
-fn get_life_support(diagnostic_report: &Vec<u32>, num_bits: usize) -> u32 {
-    let mut report_for_oxygen = diagnostic_report.clone();
-    let oxygen = search_and_filter(
-        &mut report_for_oxygen,
-        num_bits,
-        num_bits as i32 - 1,
-        true,
-    );
-
-    let mut report_for_co2 = diagnostic_report.clone();
-    let co2 = search_and_filter(
-        &mut report_for_co2,
-        num_bits,
-        num_bits as i32 - 1,
-        false,
-    );
-
-    oxygen * co2
+#[derive(Debug, PartialEq)]
+pub enum DiagnosticError {
+    // Column of the tied bit, counting from the most significant bit
+    Tie { column: usize },
+    Empty,
 }
 
-fn search_and_filter(
-    diagnostic_report: &mut Vec<u32>,
-    num_bits: usize,
-    bitpos: i32,
-    most_common: bool,
-) -> u32 {
-    if diagnostic_report.len() == 1 {
-        diagnostic_report[0]
-    } else {
-        let (num_zeros, num_ones) =
-            count_zeros_and_ones(diagnostic_report, num_bits);
-
-        let num_zeros_for_curr_bit = num_zeros[bitpos as usize];
-        let num_ones_for_curr_bit = num_ones[bitpos as usize];
-
-        let filter_val = if num_zeros_for_curr_bit > num_ones_for_curr_bit {
-            if most_common {
-                0
-            } else {
-                1
-            }
-        } else {
-            if most_common {
-                1
-            } else {
-                0
+impl From<ConsensusError> for DiagnosticError {
+    fn from(err: ConsensusError) -> Self {
+        match err {
+            ConsensusError::Tie { column } => DiagnosticError::Tie { column },
+            ConsensusError::Empty => DiagnosticError::Empty,
+            ConsensusError::UnknownSymbol { .. } => {
+                unreachable!("Both bits are in the alphabet")
             }
-        };
+        }
+    }
+}
 
-        diagnostic_report
-            .retain(|&x| (x & (1 << bitpos)) >> bitpos == filter_val);
+// Each column of the readings, most significant bit first, packed 64
+// readings to a word, reading i in bit i % 64 of word i / 64, so counting a
+// column is a popcount per 64 readings
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticReport {
+    num_readings: usize,
+    columns: Vec<Vec<u64>>,
+}
 
-        return search_and_filter(
-            diagnostic_report,
-            num_bits,
-            bitpos - 1,
-            most_common,
-        );
+impl DiagnosticReport {
+    pub fn new(readings: &Grid<bool>) -> Self {
+        let num_words = readings.num_rows().div_ceil(64);
+        let mut columns = vec![vec![0u64; num_words]; readings.num_cols()];
+        for ((row, column), bit) in readings.iter() {
+            columns[column][row / 64] |= (*bit as u64) << (row % 64);
+        }
+        DiagnosticReport {
+            num_readings: readings.num_rows(),
+            columns,
+        }
+    }
+
+    pub fn num_readings(&self) -> usize {
+        self.num_readings
+    }
+
+    // The readings one per row, unpacked
+    pub fn readings(&self) -> Grid<bool> {
+        let cells = (0..self.num_readings)
+            .flat_map(|row| {
+                self.columns
+                    .iter()
+                    .map(move |words| words[row / 64] >> (row % 64) & 1 == 1)
+            })
+            .collect();
+        Grid::from_vec(self.num_readings, self.columns.len(), cells).unwrap()
+    }
+
+    // Number of readings with each bit set
+    pub fn count_ones(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .map(|words| words.iter().map(|x| x.count_ones() as usize).sum())
+            .collect()
     }
 }
 
-fn count_zeros_and_ones(
-    diagnostic_report: &Vec<u32>,
-    num_bits: usize,
-) -> (Vec<u32>, Vec<u32>) {
-    let mut count_zeros = Vec::new();
-    let mut count_ones = Vec::new();
-    let mut idx = 0;
-    while idx < num_bits {
-        count_zeros.push(0);
-        count_ones.push(0);
-        idx += 1;
-    }
-
-    for binary_number in diagnostic_report {
-        let mut bitpos = 0;
-        while bitpos < num_bits {
-            match (binary_number & (1 << &bitpos)) > 0 {
-                false => count_zeros[bitpos as usize] += 1,
-                true => count_ones[bitpos as usize] += 1,
-            }
+// Readings can be any width but must all be the same width
+pub fn parse_diagnostic_report(
+    raw_data: &[String],
+) -> Result<DiagnosticReport, ParseGridError> {
+    let rows = raw_data
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            line.chars()
+                .map(|c| match c {
+                    '0' => Some(false),
+                    '1' => Some(true),
+                    _ => None,
+                })
+                .collect::<Option<Vec<bool>>>()
+                .ok_or(ParseGridError::InvalidValue { line: idx + 1 })
+        })
+        .collect::<Result<Vec<Vec<bool>>, _>>()?;
+
+    Ok(DiagnosticReport::new(&Grid::from_rows(rows)?))
+}
 
-            bitpos += 1;
+// The bits in tie-breaking order, the most common value of a tied bit first
+fn consensus(ties: TiePolicy) -> Consensus<bool> {
+    match ties {
+        TiePolicy::PreferZero => {
+            Consensus::new(vec![false, true], Ties::Prefer)
         }
+        TiePolicy::PreferOne => Consensus::new(vec![true, false], Ties::Prefer),
+        TiePolicy::Error => Consensus::new(vec![false, true], Ties::Error),
+        TiePolicy::SkipBit => Consensus::new(vec![false, true], Ties::Skip),
     }
+}
 
-    (count_zeros, count_ones)
+pub fn get_power_consumption(
+    diagnostic_report: &DiagnosticReport,
+    ties: TiePolicy,
+) -> Result<BigUint, DiagnosticError> {
+    let consensus = consensus(ties);
+    let num_readings = diagnostic_report.num_readings();
+    let frequencies: Vec<Vec<usize>> = diagnostic_report
+        .count_ones()
+        .into_iter()
+        .map(|num_ones| {
+            let count = |bit: &bool| match bit {
+                true => num_ones,
+                false => num_readings - num_ones,
+            };
+            consensus.alphabet().iter().map(count).collect()
+        })
+        .collect();
+
+    let gamma_rate = consensus.select(&frequencies, Keep::MostCommon)?;
+    let epsilon_rate = consensus.select(&frequencies, Keep::LeastCommon)?;
+
+    Ok(to_number(&gamma_rate) * to_number(&epsilon_rate))
+}
+
+// Oxygen keeps the readings with the most common value of each bit in turn,
+// CO2 those with the least common value
+pub fn get_life_support(
+    diagnostic_report: &DiagnosticReport,
+    ties: TiePolicy,
+) -> Result<BigUint, DiagnosticError> {
+    let readings = diagnostic_report.readings();
+    let consensus = consensus(ties);
+    let rating = |keep| -> Result<BigUint, DiagnosticError> {
+        let row = consensus.filter(&readings, keep)?;
+        Ok(to_number(&readings.row(row).to_vec()))
+    };
+
+    Ok(rating(Keep::MostCommon)? * rating(Keep::LeastCommon)?)
+}
+
+fn to_number(bits: &[bool]) -> BigUint {
+    bits.iter().fold(BigUint::default(), |number, bit| {
+        (number << 1u32) + u32::from(*bit)
+    })
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    fn get_test_input() -> Vec<u32> {
-        vec![
+    fn get_test_input() -> Vec<String> {
+        [
             "00100", "11110", "10110", "10111", "10101", "01111", "00111",
             "11100", "10000", "11001", "00010", "01010",
         ]
         .iter()
-        .map(|x| u32::from_str_radix(x, 2).unwrap())
+        .map(|x| x.to_string())
         .collect()
     }
 
+    fn report(lines: &[&str]) -> DiagnosticReport {
+        let lines: Vec<String> = lines.iter().map(|x| x.to_string()).collect();
+        parse_diagnostic_report(&lines).unwrap()
+    }
+
     #[test]
     fn test_get_power_consumption() {
-        assert_eq!(get_power_consumption(&get_test_input(), 5), 198);
+        let diagnostic_report =
+            parse_diagnostic_report(&get_test_input()).unwrap();
+        assert_eq!(
+            get_power_consumption(&diagnostic_report, TiePolicy::Error),
+            Ok(BigUint::from(198u32))
+        );
     }
 
     #[test]
     fn test_get_life_support() {
-        assert_eq!(get_life_support(&get_test_input(), 5), 230);
+        let diagnostic_report =
+            parse_diagnostic_report(&get_test_input()).unwrap();
+        assert_eq!(
+            get_life_support(&diagnostic_report, TiePolicy::PreferOne),
+            Ok(BigUint::from(230u32))
+        );
+    }
+
+    #[test]
+    fn test_wide_report() {
+        // 70 bit readings, the product doesn't fit in a u128
+        let gamma = "10".repeat(35);
+        let epsilon = "01".repeat(35);
+        let diagnostic_report = report(&[&gamma, &epsilon, &gamma]);
+
+        let gamma = BigUint::parse_bytes(gamma.as_bytes(), 2).unwrap();
+        let epsilon = BigUint::parse_bytes(epsilon.as_bytes(), 2).unwrap();
+        let expected = gamma * epsilon;
+        assert!(expected.bits() > 128);
+        assert_eq!(
+            get_power_consumption(&diagnostic_report, TiePolicy::Error),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            get_life_support(&diagnostic_report, TiePolicy::Error),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_tie_policy() {
+        // The first column is tied, the second is mostly ones
+        let diagnostic_report = report(&["01", "11", "10", "01"]);
+
+        // Gamma and epsilon are 01 and 10, 11 and 00, or 1 and 0 skipping
+        let power = |ties| get_power_consumption(&diagnostic_report, ties);
+        assert_eq!(power(TiePolicy::PreferZero), Ok(BigUint::from(2u32)));
+        assert_eq!(power(TiePolicy::PreferOne), Ok(BigUint::from(0u32)));
+        assert_eq!(power(TiePolicy::SkipBit), Ok(BigUint::from(0u32)));
+        assert_eq!(
+            power(TiePolicy::Error),
+            Err(DiagnosticError::Tie { column: 0 })
+        );
+
+        // Oxygen keeps the most common value, CO2 the least common. Oxygen
+        // and CO2 are 01 and 11, 11 and 01, or 01 and 10 skipping.
+        let life = |ties| get_life_support(&diagnostic_report, ties);
+        assert_eq!(life(TiePolicy::PreferZero), Ok(BigUint::from(3u32)));
+        assert_eq!(life(TiePolicy::PreferOne), Ok(BigUint::from(3u32)));
+        assert_eq!(life(TiePolicy::SkipBit), Ok(BigUint::from(2u32)));
+        assert_eq!(
+            life(TiePolicy::Error),
+            Err(DiagnosticError::Tie { column: 0 })
+        );
+
+        assert_eq!("skip-bit".parse(), Ok(TiePolicy::SkipBit));
+        assert_eq!("prefer-2".parse::<TiePolicy>(), Err(()));
+    }
+
+    // Straightforward versions to check the counting and search against
+    fn naive_rates(report: &Grid<bool>) -> (Vec<usize>, Vec<bool>) {
+        let counts: Vec<usize> = report
+            .columns()
+            .map(|column| column.iter().filter(|x| **x).count())
+            .collect();
+
+        let mut rows: Vec<usize> = (0..report.num_rows()).collect();
+        for column in 0..report.num_cols() {
+            let num_ones = rows.iter().filter(|row| report[(**row, column)]);
+            let keep = num_ones.count() * 2 >= rows.len();
+            rows.retain(|row| report[(*row, column)] == keep);
+        }
+
+        (counts, report.row(rows[0]).to_vec())
+    }
+
+    // Pseudo-random readings
+    fn random_lines(num_lines: usize, width: usize) -> Vec<String> {
+        let mut state: u64 = 12345;
+        (0..num_lines)
+            .map(|_| {
+                (0..width)
+                    .map(|_| {
+                        state = state
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                        if state >> 63 == 1 {
+                            '1'
+                        } else {
+                            '0'
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_large_report() {
+        // More than one word of readings per column
+        let diagnostic_report =
+            parse_diagnostic_report(&random_lines(1000, 70)).unwrap();
+        let readings = &diagnostic_report.readings();
+        let lines: Vec<String> = readings
+            .rows()
+            .map(|row| row.iter().map(|x| if *x { '1' } else { '0' }).collect())
+            .collect();
+        assert_eq!(lines, random_lines(1000, 70));
+
+        let (counts, oxygen) = naive_rates(readings);
+        assert_eq!(diagnostic_report.count_ones(), counts);
+        let consensus = consensus(TiePolicy::PreferOne);
+        let frequencies = consensus.frequencies(readings).unwrap();
+        let num_ones: Vec<usize> = frequencies.iter().map(|x| x[0]).collect();
+        assert_eq!(num_ones, counts);
+
+        let row = consensus.filter(readings, Keep::MostCommon).unwrap();
+        assert_eq!(readings.row(row).to_vec(), oxygen);
+    }
+
+    #[test]
+    fn test_parse_diagnostic_report_errors() {
+        let lines: Vec<String> = ["0101", "0111", "011", "0000"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(
+            parse_diagnostic_report(&lines),
+            Err(ParseGridError::Ragged { line: 3 })
+        );
+
+        let lines: Vec<String> =
+            ["0101", "01x1"].iter().map(|x| x.to_string()).collect();
+        assert_eq!(
+            parse_diagnostic_report(&lines),
+            Err(ParseGridError::InvalidValue { line: 2 })
+        );
+        assert_eq!(parse_diagnostic_report(&[]), Err(ParseGridError::Empty));
     }
 }
@@ -1,223 +1,458 @@
-use ndarray::Array2;
+use common::grid::{Grid, Pos};
 use regex::Regex;
 use std::fs;
 
-const NUM_ROWS: usize = 5;
-const NUM_COLS: usize = 5;
-
 pub fn main() {
-    let (numbers_drawn, bingo_boards) = parse_bingo_game("data/day04.txt");
-
-    let board_idx = find_first_winning_board(&numbers_drawn, &bingo_boards);
-    println!(
-        "day04 part1: {}",
-        get_special_value_for_winning_board(
-            &numbers_drawn,
-            &bingo_boards[board_idx]
-        )
-    );
-
-    let board_idx = find_last_winning_board(&numbers_drawn, &bingo_boards);
-    println!(
-        "day04 part2: {}",
-        get_special_value_for_winning_board(
-            &numbers_drawn,
-            &bingo_boards[board_idx]
-        )
-    );
+    let contents =
+        fs::read_to_string("data/day04.txt").expect("Could not read file.");
+    let (numbers_drawn, bingo_boards) =
+        parse_bingo_game(&contents).expect("Invalid bingo game.");
+    let rules = BingoRules::default();
+    rules.validate(&bingo_boards).expect("Invalid bingo rules.");
+
+    let winners = [
+        find_first_winning_board(&numbers_drawn, &bingo_boards, &rules),
+        find_last_winning_board(&numbers_drawn, &bingo_boards, &rules),
+    ];
+    for (part, board_idx) in winners.iter().enumerate() {
+        match board_idx.and_then(|idx| {
+            get_special_value_for_winning_board(
+                &numbers_drawn,
+                &bingo_boards[idx],
+                &rules,
+            )
+        }) {
+            Some(value) => println!("day04 part{}: {}", part + 1, value),
+            None => println!("day04 part{}: no board wins", part + 1),
+        }
+    }
 }
 
-fn parse_bingo_game(path: &str) -> (Vec<i32>, Vec<Array2<i32>>) {
-    let contents = fs::read_to_string(path).expect("Could not read file.");
+// Sets of squares that win once all of them are marked
+#[derive(Clone, Debug, PartialEq)]
+pub enum WinPattern {
+    Rows,
+    Columns,
+    // Both corner to corner diagonals, only on square boards
+    Diagonals,
+    FourCorners,
+    Blackout,
+    // Every one of the positions, which must all be on the board to win
+    Custom(Vec<Pos>),
+}
 
-    let mut lines = contents.split("\n");
+// Boards win on any one of the patterns. Free squares are marked before any
+// number is drawn and don't count towards the score; positions off the board
+// are ignored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BingoRules {
+    pub patterns: Vec<WinPattern>,
+    // The middle square of boards with an odd number of rows and columns
+    pub free_center: bool,
+    pub free_squares: Vec<Pos>,
+}
 
-    let numbers_drawn: Vec<i32> = lines
-        .next()
-        .unwrap()
-        .split(",")
-        .map(|x| x.parse::<i32>().unwrap())
-        .collect();
+// Rows and columns only, as in the puzzle
+impl Default for BingoRules {
+    fn default() -> Self {
+        BingoRules {
+            patterns: vec![WinPattern::Rows, WinPattern::Columns],
+            free_center: false,
+            free_squares: Vec::new(),
+        }
+    }
+}
 
-    // Skip the first empty line
-    lines.next().unwrap();
+#[derive(Debug, PartialEq)]
+pub enum BingoRulesError {
+    // A custom pattern without any positions
+    EmptyPattern { pattern: usize },
+    // A pattern the free squares alone complete on a board, so that board
+    // would win before anything is drawn
+    FreePattern { pattern: usize, board: usize },
+}
 
-    let mut bingo_boards = Vec::new();
+impl BingoRules {
+    // Rules a game can be played with on these boards
+    pub fn validate<T>(
+        &self,
+        bingo_boards: &[Grid<T>],
+    ) -> Result<(), BingoRulesError> {
+        for (pattern, win_pattern) in self.patterns.iter().enumerate() {
+            if *win_pattern == WinPattern::Custom(Vec::new()) {
+                return Err(BingoRulesError::EmptyPattern { pattern });
+            }
 
-    let mut curr_row = 0;
-    let mut curr_board = Array2::zeros((NUM_ROWS, NUM_COLS));
-    for line in lines {
-        if line.len() == 0 {
-            bingo_boards.push(curr_board);
-            curr_row = 0;
-            curr_board = Array2::zeros((NUM_ROWS, NUM_COLS));
-        } else {
-            let mut curr_col = 0;
-            let re = Regex::new(r"(\d+)").unwrap();
-            for cap in re.captures_iter(line) {
-                let val: &str = &cap[1];
-                curr_board[[curr_row, curr_col]] = val.parse::<i32>().unwrap();
-                curr_col += 1;
+            let rules = BingoRules {
+                patterns: vec![win_pattern.clone()],
+                ..self.clone()
+            };
+            for (board, bingo_board) in bingo_boards.iter().enumerate() {
+                let marker_board = self.marker_board(bingo_board);
+                if is_marker_board_bingo(&marker_board, &rules) {
+                    return Err(BingoRulesError::FreePattern {
+                        pattern,
+                        board,
+                    });
+                }
             }
+        }
 
-            curr_row += 1;
+        Ok(())
+    }
+
+    // Nothing drawn yet, only the free squares marked
+    fn marker_board<T>(&self, board: &Grid<T>) -> Grid<bool> {
+        let (num_rows, num_cols) = (board.num_rows(), board.num_cols());
+        let mut marker_board = Grid::new(num_rows, num_cols, false);
+
+        let center =
+            (self.free_center && num_rows % 2 == 1 && num_cols % 2 == 1)
+                .then_some((num_rows / 2, num_cols / 2));
+        for pos in self.free_squares.iter().chain(center.iter()) {
+            if let Some(marker) = marker_board.get_mut(*pos) {
+                *marker = true;
+            }
         }
+
+        marker_board
     }
+}
 
-    (numbers_drawn, bingo_boards)
+#[derive(Debug, PartialEq)]
+pub enum ParseBingoError {
+    InvalidNumber { line: usize },
+    InvalidBoardShape { line: usize },
 }
 
-fn is_marker_board_bingo(marker_board: &Array2<i32>) -> bool {
-    for row in marker_board.rows() {
-        if row.sum() == NUM_ROWS as i32 {
-            return true;
+pub fn parse_bingo_game(
+    contents: &str,
+) -> Result<(Vec<i32>, Vec<Grid<i32>>), ParseBingoError> {
+    let mut lines = contents.split('\n').enumerate();
+
+    let numbers_drawn = match lines.next() {
+        Some((_, line)) => line
+            .split(',')
+            .map(|x| x.trim().parse::<i32>())
+            .collect::<Result<Vec<i32>, _>>()
+            .map_err(|_| ParseBingoError::InvalidNumber { line: 1 })?,
+        None => return Err(ParseBingoError::InvalidNumber { line: 1 }),
+    };
+
+    let mut bingo_boards: Vec<Grid<i32>> = Vec::new();
+
+    // Boards are separated by empty lines, the first one included. The first
+    // row of the first board sets the width of every board.
+    let re = Regex::new(r"(\d+)").unwrap();
+    let mut board_start = 0;
+    let mut num_cols = 0;
+    let mut curr_board = Vec::new();
+    for (idx, line) in lines {
+        let line_num = idx + 1;
+        if line.trim().is_empty() {
+            if !curr_board.is_empty() {
+                let board = build_board(
+                    curr_board,
+                    board_start,
+                    num_cols,
+                    &bingo_boards,
+                )?;
+                bingo_boards.push(board);
+                curr_board = Vec::new();
+            }
+        } else {
+            if curr_board.is_empty() {
+                board_start = line_num;
+            }
+
+            let row_start = curr_board.len();
+            for cap in re.captures_iter(line) {
+                let val = cap[1].parse::<i32>().map_err(|_| {
+                    ParseBingoError::InvalidNumber { line: line_num }
+                })?;
+                curr_board.push(val);
+            }
+
+            let row_len = curr_board.len() - row_start;
+            if num_cols == 0 {
+                num_cols = row_len;
+            }
+            if row_len == 0 || row_len != num_cols {
+                return Err(ParseBingoError::InvalidBoardShape {
+                    line: line_num,
+                });
+            }
         }
     }
 
-    for col in marker_board.columns() {
-        if col.sum() == NUM_COLS as i32 {
-            return true;
-        }
+    // The final board may not be followed by an empty line
+    if !curr_board.is_empty() {
+        let board =
+            build_board(curr_board, board_start, num_cols, &bingo_boards)?;
+        bingo_boards.push(board);
     }
 
-    false
+    Ok((numbers_drawn, bingo_boards))
+}
+
+// Rows are already known to be num_cols wide, and every board must be as
+// tall as the first
+fn build_board(
+    values: Vec<i32>,
+    board_start: usize,
+    num_cols: usize,
+    bingo_boards: &[Grid<i32>],
+) -> Result<Grid<i32>, ParseBingoError> {
+    let num_rows = values.len() / num_cols;
+    Grid::from_vec(num_rows, num_cols, values)
+        .filter(|_| {
+            bingo_boards
+                .first()
+                .is_none_or(|x| x.num_rows() == num_rows)
+        })
+        .ok_or(ParseBingoError::InvalidBoardShape { line: board_start })
+}
+
+fn is_marker_board_bingo(
+    marker_board: &Grid<bool>,
+    rules: &BingoRules,
+) -> bool {
+    let (num_rows, num_cols) =
+        (marker_board.num_rows(), marker_board.num_cols());
+    let marked = |pos: &Pos| marker_board.get(*pos) == Some(&true);
+
+    rules.patterns.iter().any(|pattern| match pattern {
+        WinPattern::Rows => {
+            marker_board.rows().any(|row| row.iter().all(|x| *x))
+        }
+        WinPattern::Columns => {
+            marker_board.columns().any(|col| col.iter().all(|x| *x))
+        }
+        WinPattern::Diagonals => {
+            num_rows == num_cols
+                && (marker_board.diagonal().iter().all(|x| *x)
+                    || marker_board.anti_diagonal().iter().all(|x| *x))
+        }
+        WinPattern::FourCorners => [
+            (0, 0),
+            (0, num_cols - 1),
+            (num_rows - 1, 0),
+            (num_rows - 1, num_cols - 1),
+        ]
+        .iter()
+        .all(marked),
+        WinPattern::Blackout => marker_board.iter().all(|(_, x)| *x),
+        WinPattern::Custom(positions) => positions.iter().all(marked),
+    })
 }
 
+// None if no board wins. The rules are assumed valid for the boards.
 fn find_first_winning_board(
-    numbers_drawn: &Vec<i32>,
-    bingo_boards: &Vec<Array2<i32>>,
-) -> usize {
-    let mut is_drawn: Vec<Array2<i32>> = Vec::new();
-    for _ in bingo_boards {
-        is_drawn.push(Array2::zeros((NUM_ROWS, NUM_COLS)));
-    }
+    numbers_drawn: &[i32],
+    bingo_boards: &[Grid<i32>],
+    rules: &BingoRules,
+) -> Option<usize> {
+    let mut is_drawn: Vec<Grid<bool>> = bingo_boards
+        .iter()
+        .map(|board| rules.marker_board(board))
+        .collect();
 
     for num in numbers_drawn {
         for (idx, board) in bingo_boards.iter().enumerate() {
-            for i in 0..NUM_ROWS {
-                for j in 0..NUM_COLS {
-                    if board[[i, j]] == *num {
-                        is_drawn[idx][[i, j]] = 1;
-                    }
+            for (pos, value) in board.iter() {
+                if value == num {
+                    is_drawn[idx][pos] = true;
                 }
             }
 
-            if is_marker_board_bingo(&is_drawn[idx]) {
-                return idx;
+            if is_marker_board_bingo(&is_drawn[idx], rules) {
+                return Some(idx);
             }
         }
     }
 
-    panic!("No winning board found.");
+    None
 }
 
 fn find_last_winning_board(
-    numbers_drawn: &Vec<i32>,
-    bingo_boards: &Vec<Array2<i32>>,
-) -> usize {
+    numbers_drawn: &[i32],
+    bingo_boards: &[Grid<i32>],
+    rules: &BingoRules,
+) -> Option<usize> {
     let mut winning_boards: Vec<usize> = Vec::new();
 
-    let mut is_drawn: Vec<Array2<i32>> = Vec::new();
-    for _ in bingo_boards {
-        is_drawn.push(Array2::zeros((NUM_ROWS, NUM_COLS)));
-    }
+    let mut is_drawn: Vec<Grid<bool>> = bingo_boards
+        .iter()
+        .map(|board| rules.marker_board(board))
+        .collect();
 
     for num in numbers_drawn {
         for (idx, board) in bingo_boards.iter().enumerate() {
-            for i in 0..NUM_ROWS {
-                for j in 0..NUM_COLS {
-                    if board[[i, j]] == *num {
-                        is_drawn[idx][[i, j]] = 1;
-                    }
+            for (pos, value) in board.iter() {
+                if value == num {
+                    is_drawn[idx][pos] = true;
                 }
             }
 
-            if is_marker_board_bingo(&is_drawn[idx]) {
-                if !winning_boards.contains(&idx) {
-                    winning_boards.push(idx);
-                }
+            if is_marker_board_bingo(&is_drawn[idx], rules)
+                && !winning_boards.contains(&idx)
+            {
+                winning_boards.push(idx);
             }
         }
     }
 
-    winning_boards.pop().expect("No winning board found.")
+    winning_boards.pop()
 }
 
+// None if the board doesn't win
 fn get_special_value_for_winning_board(
-    numbers_drawn: &Vec<i32>,
-    winning_board: &Array2<i32>,
-) -> i32 {
-    let mut marker_board = Array2::zeros((NUM_ROWS, NUM_COLS));
+    numbers_drawn: &[i32],
+    winning_board: &Grid<i32>,
+    rules: &BingoRules,
+) -> Option<i32> {
+    let mut marker_board = rules.marker_board(winning_board);
     let mut cleared_board = winning_board.clone();
+    for (pos, marked) in marker_board.iter() {
+        if *marked {
+            cleared_board[pos] = 0;
+        }
+    }
 
     for num in numbers_drawn {
-        for i in 0..NUM_ROWS {
-            for j in 0..NUM_COLS {
-                if winning_board[[i, j]] == *num {
-                    marker_board[[i, j]] = 1;
-                    cleared_board[[i, j]] = 0;
-                }
+        for (pos, value) in winning_board.iter() {
+            if value == num {
+                marker_board[pos] = true;
+                cleared_board[pos] = 0;
             }
         }
 
-        if is_marker_board_bingo(&marker_board) {
-            return *num * cleared_board.sum();
+        if is_marker_board_bingo(&marker_board, rules) {
+            return Some(*num * cleared_board.sum());
         }
     }
 
-    panic!("Board did not win.");
+    None
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use ndarray::arr2;
 
-    fn get_test_input() -> (Vec<i32>, Vec<Array2<i32>>) {
+    fn get_test_input() -> (Vec<i32>, Vec<Grid<i32>>) {
         let numbers_drawn = vec![
             7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24, 10, 16, 13, 6, 15, 25,
             12, 22, 18, 20, 8, 19, 3, 26, 1,
         ];
 
-        let b1 = arr2(&[
-            [22, 13, 17, 11, 0],
-            [8, 2, 23, 4, 24],
-            [21, 9, 14, 16, 7],
-            [6, 10, 3, 18, 5],
-            [1, 12, 20, 15, 19],
-        ]);
-
-        let b2 = arr2(&[
-            [3, 15, 0, 2, 22],
-            [9, 18, 13, 17, 5],
-            [19, 8, 7, 25, 23],
-            [20, 11, 10, 24, 4],
-            [14, 21, 16, 12, 6],
-        ]);
-
-        let b3 = arr2(&[
-            [14, 21, 17, 24, 4],
-            [10, 16, 15, 9, 19],
-            [18, 8, 23, 26, 20],
-            [22, 11, 13, 6, 5],
-            [2, 0, 12, 3, 7],
-        ]);
+        let b1 = Grid::from_rows(vec![
+            vec![22, 13, 17, 11, 0],
+            vec![8, 2, 23, 4, 24],
+            vec![21, 9, 14, 16, 7],
+            vec![6, 10, 3, 18, 5],
+            vec![1, 12, 20, 15, 19],
+        ])
+        .unwrap();
+
+        let b2 = Grid::from_rows(vec![
+            vec![3, 15, 0, 2, 22],
+            vec![9, 18, 13, 17, 5],
+            vec![19, 8, 7, 25, 23],
+            vec![20, 11, 10, 24, 4],
+            vec![14, 21, 16, 12, 6],
+        ])
+        .unwrap();
+
+        let b3 = Grid::from_rows(vec![
+            vec![14, 21, 17, 24, 4],
+            vec![10, 16, 15, 9, 19],
+            vec![18, 8, 23, 26, 20],
+            vec![22, 11, 13, 6, 5],
+            vec![2, 0, 12, 3, 7],
+        ])
+        .unwrap();
 
         let bingo_boards = vec![b1, b2, b3];
 
         (numbers_drawn, bingo_boards)
     }
 
+    #[test]
+    fn test_parse_bingo_game() {
+        let contents = "7,4,9\n\n22 13 17 11  0\n 8  2 23  4 24\n\
+                        21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19";
+        let (numbers_drawn, bingo_boards) = parse_bingo_game(contents).unwrap();
+        assert_eq!(numbers_drawn, vec![7, 4, 9]);
+        assert_eq!(bingo_boards, vec![get_test_input().1[0].clone()]);
+    }
+
+    #[test]
+    fn test_parse_bingo_game_invalid_number() {
+        assert_eq!(
+            parse_bingo_game(""),
+            Err(ParseBingoError::InvalidNumber { line: 1 })
+        );
+        assert_eq!(
+            parse_bingo_game("1,2\n\n1 2 3 4 99999999999"),
+            Err(ParseBingoError::InvalidNumber { line: 3 })
+        );
+    }
+
+    #[test]
+    fn test_parse_bingo_game_invalid_board_shape() {
+        assert_eq!(
+            parse_bingo_game("1\n\n1 2 3\n4 5"),
+            Err(ParseBingoError::InvalidBoardShape { line: 4 })
+        );
+        assert_eq!(
+            parse_bingo_game("1\n\n1 2 3\n4 5 6\n\n7 8 9"),
+            Err(ParseBingoError::InvalidBoardShape { line: 6 })
+        );
+        assert_eq!(
+            parse_bingo_game("1\n\n1 2\n\n3 4 5"),
+            Err(ParseBingoError::InvalidBoardShape { line: 5 })
+        );
+    }
+
+    #[test]
+    fn test_parse_bingo_game_board_size() {
+        let (_, bingo_boards) =
+            parse_bingo_game("1\n\n1 2 3\n4 5 6\n\n7 8 9\n10 11 12\n").unwrap();
+        assert_eq!(bingo_boards.len(), 2);
+        assert_eq!(bingo_boards[1].row(1).to_vec(), vec![10, 11, 12]);
+    }
+
     #[test]
     fn test_find_first_winning_board() {
         let (numbers_drawn, bingo_boards) = get_test_input();
-        assert_eq!(find_first_winning_board(&numbers_drawn, &bingo_boards), 2);
+        let rules = BingoRules::default();
+        assert_eq!(
+            find_first_winning_board(&numbers_drawn, &bingo_boards, &rules),
+            Some(2)
+        );
+        // Not enough numbers drawn for any board to win
+        assert_eq!(
+            find_first_winning_board(
+                &numbers_drawn[..4],
+                &bingo_boards,
+                &rules
+            ),
+            None
+        );
     }
 
     #[test]
     fn test_find_last_winning_board() {
         let (numbers_drawn, bingo_boards) = get_test_input();
-        assert_eq!(find_last_winning_board(&numbers_drawn, &bingo_boards), 1);
+        let rules = BingoRules::default();
+        assert_eq!(
+            find_last_winning_board(&numbers_drawn, &bingo_boards, &rules),
+            Some(1)
+        );
+        assert_eq!(
+            find_last_winning_board(&numbers_drawn[..4], &bingo_boards, &rules),
+            None
+        );
     }
 
     #[test]
@@ -225,8 +460,155 @@ mod test {
         let (numbers_drawn, bingo_boards) = get_test_input();
         let winning_board = &bingo_boards[2];
         assert_eq!(
-            get_special_value_for_winning_board(&numbers_drawn, winning_board),
-            4512
+            get_special_value_for_winning_board(
+                &numbers_drawn,
+                winning_board,
+                &BingoRules::default()
+            ),
+            Some(4512)
+        );
+        assert_eq!(
+            get_special_value_for_winning_board(
+                &numbers_drawn,
+                &bingo_boards[0],
+                &BingoRules {
+                    patterns: Vec::new(),
+                    ..BingoRules::default()
+                }
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_win_patterns() {
+        let (numbers_drawn, bingo_boards) = get_test_input();
+        let play = |patterns| {
+            let rules = BingoRules {
+                patterns,
+                ..BingoRules::default()
+            };
+            let first =
+                find_first_winning_board(&numbers_drawn, &bingo_boards, &rules)
+                    .unwrap();
+            let last =
+                find_last_winning_board(&numbers_drawn, &bingo_boards, &rules)
+                    .unwrap();
+            let score = get_special_value_for_winning_board(
+                &numbers_drawn,
+                &bingo_boards[first],
+                &rules,
+            )
+            .unwrap();
+            (first, last, score)
+        };
+
+        // The third board wins sooner on its 14, 9, 23, 6, 2 diagonal
+        let mut patterns = BingoRules::default().patterns;
+        patterns.push(WinPattern::Diagonals);
+        assert_eq!(play(patterns), (2, 1, 494));
+        assert_eq!(play(vec![WinPattern::FourCorners]), (2, 0, 3262));
+        // Every number on the second board is drawn first
+        assert_eq!(play(vec![WinPattern::Blackout]), (1, 0, 0));
+        let custom = WinPattern::Custom(vec![(0, 0), (1, 1)]);
+        assert_eq!(play(vec![custom]), (2, 1, 2592));
+        // Off the board, so it can never win
+        let custom = WinPattern::Custom(vec![(0, 0), (5, 5)]);
+        let rules = BingoRules {
+            patterns: vec![custom],
+            ..BingoRules::default()
+        };
+        let marker_board = bingo_boards[0].map(|_| true);
+        assert!(!is_marker_board_bingo(&marker_board, &rules));
+    }
+
+    #[test]
+    fn test_free_squares() {
+        let board =
+            Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]])
+                .unwrap();
+        let mut rules = BingoRules {
+            patterns: vec![WinPattern::Diagonals],
+            free_center: true,
+            free_squares: Vec::new(),
+        };
+
+        // The center doesn't count towards the score
+        assert_eq!(
+            get_special_value_for_winning_board(&[1, 9], &board, &rules),
+            Some(9 * (2 + 3 + 4 + 6 + 7 + 8))
+        );
+
+        rules.free_center = false;
+        rules.free_squares = vec![(0, 2), (1, 1), (9, 9)];
+        assert_eq!(
+            get_special_value_for_winning_board(&[4, 7], &board, &rules),
+            Some(7 * (1 + 2 + 6 + 8 + 9))
+        );
+
+        // Rectangular boards have no diagonals, even sized ones no center
+        rules.free_center = true;
+        rules.free_squares = Vec::new();
+        let board = Grid::new(3, 5, 1);
+        assert_eq!(
+            rules.marker_board(&board).iter().filter(|x| *x.1).count(),
+            1
+        );
+        assert!(!is_marker_board_bingo(&board.map(|_| true), &rules));
+        assert_eq!(
+            rules.marker_board(&Grid::new(4, 4, 1)),
+            Grid::new(4, 4, false)
+        );
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        let (_, bingo_boards) = get_test_input();
+        let mut rules = BingoRules::default();
+        assert_eq!(rules.validate(&bingo_boards), Ok(()));
+
+        rules.patterns.push(WinPattern::Custom(Vec::new()));
+        assert_eq!(
+            rules.validate(&bingo_boards),
+            Err(BingoRulesError::EmptyPattern { pattern: 2 })
+        );
+
+        // Free squares alone would complete the first column
+        rules.patterns = vec![WinPattern::Diagonals, WinPattern::Columns];
+        rules.free_squares = (0..5).map(|x| (x, 0)).collect();
+        assert_eq!(
+            rules.validate(&bingo_boards),
+            Err(BingoRulesError::FreePattern {
+                pattern: 1,
+                board: 0
+            })
+        );
+
+        // Only free on the one by one board
+        let boards = [Grid::new(5, 5, 0), Grid::new(1, 1, 0)];
+        rules.patterns = vec![WinPattern::Blackout];
+        rules.free_squares = Vec::new();
+        rules.free_center = true;
+        assert_eq!(
+            rules.validate(&boards),
+            Err(BingoRulesError::FreePattern {
+                pattern: 0,
+                board: 1
+            })
+        );
+    }
+
+    // Crash reproducers found by the parse_bingo_game fuzz target
+    #[test]
+    fn test_parse_bingo_game_fuzz_regressions() {
+        assert_eq!(parse_bingo_game("7"), Ok((vec![7], Vec::new())));
+        assert_eq!(
+            parse_bingo_game("7,x"),
+            Err(ParseBingoError::InvalidNumber { line: 1 })
+        );
+        assert_eq!(
+            parse_bingo_game("7\n\n1 2 3 4 5\n1 2 3 4 5 6"),
+            Err(ParseBingoError::InvalidBoardShape { line: 4 })
         );
     }
 }
@@ -1,3 +1,4 @@
+use common::solution::{Solution, Tag};
 use std::fs;
 
 pub mod day01;
@@ -5,6 +6,41 @@ pub mod day02;
 pub mod day03;
 pub mod day04;
 
+pub const SOLUTIONS: &[Solution] = &[
+    Solution {
+        year: 2021,
+        day: 1,
+        title: "Sonar Sweep",
+        tags: &[Tag::SlidingWindow],
+        answers: [Some("1681"), Some("1704")],
+        main: day01::main,
+    },
+    Solution {
+        year: 2021,
+        day: 2,
+        title: "Dive!",
+        tags: &[Tag::Parsing, Tag::Simulation],
+        answers: [Some("2039912"), Some("1942068080")],
+        main: day02::main,
+    },
+    Solution {
+        year: 2021,
+        day: 3,
+        title: "Binary Diagnostic",
+        tags: &[Tag::Bits],
+        answers: [Some("2972336"), Some("3368358")],
+        main: day03::main,
+    },
+    Solution {
+        year: 2021,
+        day: 4,
+        title: "Giant Squid",
+        tags: &[Tag::Parsing, Tag::Grid, Tag::Simulation],
+        answers: [Some("8136"), Some("12738")],
+        main: day04::main,
+    },
+];
+
 pub fn text_file_to_vec(path: &str) -> Vec<String> {
     let contents = fs::read_to_string(path).expect("Could not read file.");
 
@@ -1,8 +1,15 @@
-use year2021::*;
+use std::env;
+use year2021::SOLUTIONS;
 
 fn main() {
-    day01::main();
-    day02::main();
-    day03::main();
-    day04::main();
+    match env::args().nth(1) {
+        Some(day) => {
+            let day = day.parse::<u32>().expect("Invalid day.");
+            match SOLUTIONS.iter().find(|solution| solution.day == day) {
+                Some(solution) => (solution.main)(),
+                None => panic!("No solution for day {}.", day),
+            }
+        }
+        None => SOLUTIONS.iter().for_each(|solution| (solution.main)()),
+    }
 }
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day03::{parse_rucksack_compartments, parse_rucksacks};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let raw_data: Vec<String> = s.split('\n').map(String::from).collect();
+        let _ = parse_rucksacks(&raw_data);
+        let _ = parse_rucksack_compartments(&raw_data);
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day05::parse_stack_and_steps;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let raw_data: Vec<String> = s.split('\n').map(String::from).collect();
+        let _ = parse_stack_and_steps(&raw_data);
+    }
+});
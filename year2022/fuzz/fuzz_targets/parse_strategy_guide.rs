@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day02::parse_strategy_guide;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let raw_data: Vec<String> = s.split('\n').map(String::from).collect();
+        let _ = parse_strategy_guide(&raw_data, true);
+        let _ = parse_strategy_guide(&raw_data, false);
+    }
+});
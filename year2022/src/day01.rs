@@ -1,4 +1,5 @@
 use super::text_file_to_vec;
+use common::top_k::TopK;
 
 pub fn main() {
     let raw_data = text_file_to_vec("data/day01.txt");
@@ -10,12 +11,12 @@ pub fn main() {
     assert_eq!(max_calories, 213958);
 }
 
-fn get_elf_calories(raw_data: &Vec<String>) -> Vec<u32> {
+fn get_elf_calories(raw_data: &[String]) -> Vec<u32> {
     let mut elf_calories = Vec::<u32>::new();
 
     let mut curr_calories = 0;
     for line in raw_data {
-        if line.len() == 0 {
+        if line.is_empty() {
             elf_calories.push(curr_calories);
             curr_calories = 0;
         } else {
@@ -30,16 +31,22 @@ fn get_elf_calories(raw_data: &Vec<String>) -> Vec<u32> {
     elf_calories
 }
 
-fn find_max_calories(raw_data: &Vec<String>) -> u32 {
+fn find_max_calories(raw_data: &[String]) -> u32 {
     let elf_calories = get_elf_calories(raw_data);
     *elf_calories.iter().max().unwrap()
 }
 
-fn find_calories_of_top_groups(raw_data: &Vec<String>) -> u32 {
-    let mut elf_calories = get_elf_calories(raw_data);
-    elf_calories.sort();
-    elf_calories.reverse();
-    elf_calories[0] + elf_calories[1] + elf_calories[2]
+fn find_calories_of_top_groups(raw_data: &[String]) -> u32 {
+    find_top_elves(raw_data, 3)
+        .iter()
+        .map(|(_, calories)| calories)
+        .sum()
+}
+
+// The k elves carrying the most calories as (elf index, calories), most
+// calories first
+fn find_top_elves(raw_data: &[String], k: usize) -> Vec<(usize, u32)> {
+    get_elf_calories(raw_data).into_iter().top_k_indexed(k)
 }
 
 #[cfg(test)]
@@ -67,4 +74,12 @@ mod test {
     fn test_find_calories_of_top_groups() {
         assert_eq!(find_calories_of_top_groups(&get_test_input()), 45000);
     }
+
+    #[test]
+    fn test_find_top_elves() {
+        assert_eq!(
+            find_top_elves(&get_test_input(), 3),
+            vec![(3, 24000), (2, 11000), (4, 10000)]
+        );
+    }
 }
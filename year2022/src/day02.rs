@@ -13,15 +13,15 @@ pub fn main() {
 
 type Score = u32;
 
-#[derive(Clone, Copy, PartialEq)]
-enum Shape {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
     Rock,
     Paper,
     Scissors,
 }
 
-#[derive(Clone, Copy, PartialEq)]
-enum Outcome {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
     Win,
     Loss,
     Draw,
@@ -103,24 +103,50 @@ fn find_hand(outcome: Outcome, opponent: Shape) -> Shape {
     }
 }
 
-fn score_strategy_guide(strategy_guide: &Vec<String>, part1: bool) -> Score {
-    let mut score = 0;
-    for line in strategy_guide {
-        if line.len() == 0 {
+#[derive(Debug, PartialEq)]
+pub enum ParseStrategyError {
+    InvalidRound { line: usize },
+}
+
+/// Parse each round of the strategy guide into (opponent, player) shapes,
+/// stopping at the first empty line.
+pub fn parse_strategy_guide(
+    strategy_guide: &[String],
+    part1: bool,
+) -> Result<Vec<(Shape, Shape)>, ParseStrategyError> {
+    let mut rounds = Vec::new();
+    for (idx, line) in strategy_guide.iter().enumerate() {
+        if line.is_empty() {
             break;
         }
-        let mut split = line.split_whitespace();
-        let opponent = Shape::from_str(split.next().unwrap()).unwrap();
+
+        let invalid_round =
+            || ParseStrategyError::InvalidRound { line: idx + 1 };
+        let (opponent, player) =
+            match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                [opponent, player] => (opponent, player),
+                _ => return Err(invalid_round()),
+            };
+
+        let opponent =
+            Shape::from_str(opponent).map_err(|_| invalid_round())?;
         let player = if part1 {
-            Shape::from_str(split.next().unwrap()).unwrap()
+            Shape::from_str(player)
         } else {
-            let outcome = Outcome::from_str(split.next().unwrap()).unwrap();
-            find_hand(outcome, opponent)
+            Outcome::from_str(player).map(|x| find_hand(x, opponent))
         };
-        score += score_hand(player, opponent);
+        rounds.push((opponent, player.map_err(|_| invalid_round())?));
     }
 
-    score
+    Ok(rounds)
+}
+
+fn score_strategy_guide(strategy_guide: &[String], part1: bool) -> Score {
+    parse_strategy_guide(strategy_guide, part1)
+        .expect("Invalid strategy guide.")
+        .into_iter()
+        .map(|(opponent, player)| score_hand(player, opponent))
+        .sum()
 }
 
 #[cfg(test)]
@@ -128,7 +154,7 @@ mod test {
     use super::*;
 
     fn get_test_input() -> Vec<String> {
-        vec!["A Y", "B X", "C Z"]
+        ["A Y", "B X", "C Z"]
             .iter()
             .map(|x| x.to_string())
             .collect()
@@ -143,4 +169,28 @@ mod test {
     fn test_score_strategy_guide_part2() {
         assert_eq!(score_strategy_guide(&get_test_input(), false), 12);
     }
+
+    #[test]
+    fn test_parse_strategy_guide_invalid_round() {
+        for line in ["A", "A Y Z", "D Y", "AY", "A Q"] {
+            let strategy_guide = vec!["A Y".to_string(), line.to_string()];
+            for part1 in [true, false] {
+                assert_eq!(
+                    parse_strategy_guide(&strategy_guide, part1),
+                    Err(ParseStrategyError::InvalidRound { line: 2 })
+                );
+            }
+        }
+    }
+
+    // Crash reproducers found by the parse_strategy_guide fuzz target
+    #[test]
+    fn test_parse_strategy_guide_fuzz_regressions() {
+        for line in ["A", " ", "é X"] {
+            assert_eq!(
+                parse_strategy_guide(&[line.to_string()], false),
+                Err(ParseStrategyError::InvalidRound { line: 1 })
+            );
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use super::text_file_to_vec;
+use common::bitset::BitSet;
 
 pub fn main() {
     let raw_data = text_file_to_vec("data/day03.txt");
@@ -10,101 +11,100 @@ pub fn main() {
     assert_eq!(result, 2276);
 }
 
-fn find_sum_of_duplicate_items(raw_data: &Vec<String>) -> u32 {
+fn find_sum_of_duplicate_items(raw_data: &[String]) -> u32 {
+    let compartments =
+        parse_rucksack_compartments(raw_data).expect("Invalid rucksack list.");
+
     let mut sum = 0;
-    for (c1, c2) in parse_rucksack_compartments(&raw_data) {
-        let dups = find_duplicate_items(&c1, &c2);
+    for (c1, c2) in compartments {
+        let dups = c1 & c2;
         assert!(dups.len() <= 1);
-        sum += dups.iter().sum::<u32>();
+        sum += dups.iter().sum::<usize>() as u32;
     }
 
     sum
 }
 
-fn find_sum_of_badge_groups(raw_data: &Vec<String>) -> u32 {
-    let rucksacks = parse_rucksacks(&raw_data);
+fn find_sum_of_badge_groups(raw_data: &[String]) -> u32 {
+    let rucksacks = parse_rucksacks(raw_data).expect("Invalid rucksack list.");
 
     let mut sum = 0;
-    let mut index = 0;
-    while index < rucksacks.len() {
-        let elf1 = &rucksacks[index];
-        let elf2 = &rucksacks[index + 1];
-        let elf3 = &rucksacks[index + 2];
-        sum += find_common_item(&elf1, &elf2, &elf3);
-        index += 3;
+    for group in rucksacks.chunks(3) {
+        sum += find_common_item(group) as u32;
     }
 
     sum
 }
 
-fn find_common_item(
-    rucksack1: &Vec<u32>,
-    rucksack2: &Vec<u32>,
-    rucksack3: &Vec<u32>,
-) -> u32 {
-    let dup_set1 = find_duplicate_items(rucksack1, rucksack2);
-    let dup_set2 = find_duplicate_items(&dup_set1, rucksack3);
-    assert_eq!(dup_set2.len(), 1);
-    dup_set2[0]
+fn find_common_item(rucksacks: &[Rucksack]) -> usize {
+    let shared = rucksacks
+        .iter()
+        .copied()
+        .reduce(|acc, rucksack| acc & rucksack)
+        .unwrap_or_default();
+    assert_eq!(shared.len(), 1);
+    shared.iter().next().unwrap()
 }
 
-fn find_duplicate_items(
-    compartment1: &Vec<u32>,
-    compartment2: &Vec<u32>,
-) -> Vec<u32> {
-    let mut dups = Vec::new();
-    for item1 in compartment1 {
-        match compartment2.into_iter().find(|&item2| item2 == item1) {
-            Some(duplicate) => {
-                // Avoid duplicates in the result
-                match dups.iter().find(|&&dup| dup == *duplicate) {
-                    Some(_) => (),
-                    None => dups.push(*duplicate),
-                };
-            }
-            None => continue,
-        }
-    }
+// Item priorities 1-52
+pub type Rucksack = BitSet;
 
-    dups
+#[derive(Debug, PartialEq)]
+pub enum ParseRucksackError {
+    InvalidItem { line: usize },
+    OddLength { line: usize },
 }
 
-fn item_to_priority(item: char) -> u32 {
-    let converted = item as u32;
-    // Convert from ASCII table values to priorit
-    if 97 <= converted && converted <= 122 {
-        converted - 96
-    } else {
-        converted - 64 + 26
+fn item_to_priority(item: char) -> Option<usize> {
+    match item {
+        'a'..='z' => Some(item as usize - 'a' as usize + 1),
+        'A'..='Z' => Some(item as usize - 'A' as usize + 27),
+        _ => None,
     }
 }
 
-fn parse_rucksacks(raw_data: &Vec<String>) -> Vec<Vec<u32>> {
+fn parse_items(
+    rucksack: &str,
+    line: usize,
+) -> Result<Vec<usize>, ParseRucksackError> {
+    rucksack
+        .chars()
+        .map(|i| {
+            item_to_priority(i).ok_or(ParseRucksackError::InvalidItem { line })
+        })
+        .collect()
+}
+
+pub fn parse_rucksacks(
+    raw_data: &[String],
+) -> Result<Vec<Rucksack>, ParseRucksackError> {
     raw_data
         .iter()
-        .map(|rucksack| rucksack.chars().map(|i| item_to_priority(i)).collect())
+        .enumerate()
+        .map(|(idx, rucksack)| {
+            let items = parse_items(rucksack, idx + 1)?;
+            Ok(items.into_iter().collect())
+        })
         .collect()
 }
 
-fn parse_rucksack_compartments(
-    raw_data: &Vec<String>,
-) -> Vec<(Vec<u32>, Vec<u32>)> {
+pub fn parse_rucksack_compartments(
+    raw_data: &[String],
+) -> Result<Vec<(Rucksack, Rucksack)>, ParseRucksackError> {
     raw_data
         .iter()
-        .map(|rucksack| {
-            let index = rucksack.len() / 2;
-            let compartments = rucksack.split_at(index);
-            let compartment1 = compartments
-                .0
-                .chars()
-                .map(|i| item_to_priority(i))
-                .collect();
-            let compartment2 = compartments
-                .1
-                .chars()
-                .map(|i| item_to_priority(i))
-                .collect();
-            (compartment1, compartment2)
+        .enumerate()
+        .map(|(idx, rucksack)| {
+            let items = parse_items(rucksack, idx + 1)?;
+            if items.len() % 2 != 0 {
+                return Err(ParseRucksackError::OddLength { line: idx + 1 });
+            }
+
+            let (compartment1, compartment2) = items.split_at(items.len() / 2);
+            Ok((
+                compartment1.iter().copied().collect(),
+                compartment2.iter().copied().collect(),
+            ))
         })
         .collect()
 }
@@ -114,7 +114,7 @@ mod test {
     use super::*;
 
     fn get_test_input() -> Vec<String> {
-        vec![
+        [
             "vJrwpWtwJgWrhcsFMMfFFhFp",
             "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
             "PmmdzqPrVvPwwTWBwg",
@@ -136,4 +136,48 @@ mod test {
     fn test_find_sum_of_badge_groups() {
         assert_eq!(find_sum_of_badge_groups(&get_test_input()), 70);
     }
+
+    #[test]
+    fn test_parse_rucksacks_invalid_item() {
+        for rucksack in
+            ["vJrw0WtwJgWrhcsF", "vJrw WtwJgWrhcsF", "vJrwéWtwJgWrhcs"]
+        {
+            let raw_data =
+                vec!["ttgJtRGJQctTZtZT".to_string(), rucksack.to_string()];
+            assert_eq!(
+                parse_rucksacks(&raw_data),
+                Err(ParseRucksackError::InvalidItem { line: 2 })
+            );
+            assert_eq!(
+                parse_rucksack_compartments(&raw_data),
+                Err(ParseRucksackError::InvalidItem { line: 2 })
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_rucksack_compartments_odd_length() {
+        let raw_data = vec!["ttgJtRGJQctTZtZ".to_string()];
+        assert_eq!(
+            parse_rucksack_compartments(&raw_data),
+            Err(ParseRucksackError::OddLength { line: 1 })
+        );
+    }
+
+    // Crash reproducers found by the parse_rucksacks fuzz target: priorities
+    // underflowed for digits, and compartments were split inside a character
+    #[test]
+    fn test_parse_rucksacks_fuzz_regressions() {
+        for rucksack in ["0", "éa"] {
+            let raw_data = vec![rucksack.to_string()];
+            assert_eq!(
+                parse_rucksacks(&raw_data),
+                Err(ParseRucksackError::InvalidItem { line: 1 })
+            );
+            assert_eq!(
+                parse_rucksack_compartments(&raw_data),
+                Err(ParseRucksackError::InvalidItem { line: 1 })
+            );
+        }
+    }
 }
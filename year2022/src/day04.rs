@@ -1,4 +1,5 @@
 use super::text_file_to_vec;
+use common::interval::Interval;
 use std::str::FromStr;
 
 pub fn main() {
@@ -11,52 +12,23 @@ pub fn main() {
     assert_eq!(result, 883);
 }
 
-struct Range {
-    min: u32,
-    max: u32,
-}
-
-impl FromStr for Range {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_once("-") {
-            Some((min, max)) => {
-                match (u32::from_str(min), u32::from_str(max)) {
-                    (Ok(min), Ok(max)) => Ok(Range { min, max }),
-                    (_, _) => Err(()),
-                }
-            }
-            None => Err(()),
-        }
-    }
-}
+pub type Range = Interval<u32>;
 
-impl Range {
-    fn contains(&self, other: &Range) -> bool {
-        self.min <= other.min && self.max >= other.max
-    }
-
-    fn overlaps(&self, other: &Range) -> bool {
-        self.min <= other.max && self.max >= other.min
-    }
-}
-
-fn count_full_overlap(raw_data: &Vec<String>) -> i32 {
+fn count_full_overlap(raw_data: &[String]) -> i32 {
     raw_data
-        .into_iter()
+        .iter()
         .map(|elf_pair| {
             let (left_elf, right_elf) = elf_pair.split_once(",").unwrap();
             let left_elf = Range::from_str(left_elf).unwrap();
             let right_elf = Range::from_str(right_elf).unwrap();
-            (left_elf.contains(&right_elf) || right_elf.contains(&left_elf))
-                as i32
+            (left_elf.covers(&right_elf) || right_elf.covers(&left_elf)) as i32
         })
         .sum()
 }
 
-fn count_partial_overlap(raw_data: &Vec<String>) -> i32 {
+fn count_partial_overlap(raw_data: &[String]) -> i32 {
     raw_data
-        .into_iter()
+        .iter()
         .map(|elf_pair| {
             let (left_elf, right_elf) = elf_pair.split_once(",").unwrap();
             let left_elf = Range::from_str(left_elf).unwrap();
@@ -65,3 +37,35 @@ fn count_partial_overlap(raw_data: &Vec<String>) -> i32 {
         })
         .sum()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_test_input() -> Vec<String> {
+        [
+            "2-4,6-8", "2-3,4-5", "5-7,7-9", "2-8,3-7", "6-6,4-6", "2-6,4-8",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_count_full_overlap() {
+        assert_eq!(count_full_overlap(&get_test_input()), 2);
+    }
+
+    #[test]
+    fn test_count_partial_overlap() {
+        assert_eq!(count_partial_overlap(&get_test_input()), 4);
+    }
+
+    #[test]
+    fn test_range_from_str() {
+        assert_eq!(Range::from_str("6-6"), Ok(Range::point(6)));
+        for s in ["", "-", "6", "6-", "-6", "a-6", "8-6", "1-99999999999"] {
+            assert_eq!(Range::from_str(s), Err(()));
+        }
+    }
+}
@@ -5,19 +5,21 @@ use std::str::FromStr;
 pub fn main() {
     let raw_data = text_file_to_vec("data/day05.txt");
 
-    let (mut stack, steps) = parse_stack_and_steps(&raw_data);
+    let (mut stack, steps) =
+        parse_stack_and_steps(&raw_data).expect("Invalid stack and steps.");
     let result = apply_steps_and_get_top_crates(&mut stack, steps, false);
     assert_eq!(result, "CNSZFDVLJ");
 
-    let (mut stack, steps) = parse_stack_and_steps(&raw_data);
+    let (mut stack, steps) =
+        parse_stack_and_steps(&raw_data).expect("Invalid stack and steps.");
     let result = apply_steps_and_get_top_crates(&mut stack, steps, true);
     assert_eq!(result, "QNDWLMGNS");
 }
 
-type Stack = BTreeMap<u32, Vec<char>>;
+pub type Stack = BTreeMap<u32, Vec<char>>;
 
 #[derive(Debug)]
-struct Step {
+pub struct Step {
     src: u32,
     dst: u32,
     cnt: u32,
@@ -32,10 +34,9 @@ fn apply_steps_and_get_top_crates(
         // Pop "step.cnt" chars from crates[src] into a temp variable
         let mut popped_crates = String::new();
         for _ in 0..step.cnt {
-            match stack.get_mut(&step.src).unwrap().pop() {
-                Some(c) => popped_crates.push(c),
-                // "step.src" has no more elements, just skip and move on
-                None => (),
+            // "step.src" may have no more elements, just skip and move on
+            if let Some(c) = stack.get_mut(&step.src).unwrap().pop() {
+                popped_crates.push(c);
             }
         }
 
@@ -53,80 +54,88 @@ fn apply_steps_and_get_top_crates(
 
     // Get the top crate for each stack
     let mut result = String::new();
-    for (_, crates) in stack {
-        if crates.len() > 0 {
-            result.push(crates.pop().unwrap());
+    for crates in stack.values_mut() {
+        if let Some(c) = crates.pop() {
+            result.push(c);
         }
     }
 
     result
 }
 
-fn parse_stack_and_steps(raw_data: &Vec<String>) -> (Stack, Vec<Step>) {
+#[derive(Debug, PartialEq)]
+pub enum ParseStackError {
+    UnexpectedEnd,
+    InvalidCrate { line: usize },
+    InvalidStep { line: usize },
+    UnknownStack { line: usize },
+}
+
+pub fn parse_stack_and_steps(
+    raw_data: &[String],
+) -> Result<(Stack, Vec<Step>), ParseStackError> {
     let mut stack = Stack::new();
     let mut steps = Vec::<Step>::new();
 
-    let mut raw_data = raw_data.iter();
+    let mut raw_data = raw_data.iter().enumerate();
 
     // Parse stack: Note that I've inserted dummy crates ("[x]") to simplify parsing
-    let mut line = raw_data.next().unwrap();
-    while line.chars().nth(0).unwrap() == '[' {
+    let (mut idx, mut line) =
+        raw_data.next().ok_or(ParseStackError::UnexpectedEnd)?;
+    while line.starts_with('[') {
         // Iterate over each stack
         for (stack_num, crate_val) in line.split_whitespace().enumerate() {
             // Crate syntax is "[A]", so second char is the label
-            let crate_val = crate_val.chars().nth(1).unwrap();
-            if crate_val == 'x' {
-                // Dummy value, skip
-                continue;
-            } else {
-                // Otherwise insert into the stack
-                match stack.get_mut(&((stack_num + 1) as u32)) {
-                    Some(v) => v.push(crate_val),
-                    None => {
-                        let mut new_stack = Vec::new();
-                        new_stack.push(crate_val);
-                        stack.insert((stack_num + 1) as u32, new_stack);
-                    }
+            let crate_val = match crate_val.as_bytes() {
+                [b'[', c, b']'] => *c as char,
+                _ => {
+                    return Err(ParseStackError::InvalidCrate { line: idx + 1 })
                 }
+            };
+
+            // Create every stack we see, even if it only holds dummy crates
+            let crates = stack.entry((stack_num + 1) as u32).or_default();
+            if crate_val != 'x' {
+                crates.push(crate_val);
             }
         }
 
-        line = raw_data.next().unwrap();
+        (idx, line) = raw_data.next().ok_or(ParseStackError::UnexpectedEnd)?;
     }
 
-    // Skip line with stack numbers
-    let _ = raw_data.next().unwrap();
-
-    // Skip empty line
-    let mut line = raw_data.next();
+    // Skip empty line, "line" already holds the stack numbers
+    let _ = raw_data.next().ok_or(ParseStackError::UnexpectedEnd)?;
 
     // Parse steps
-    loop {
-        match line {
-            Some(step) => {
-                // move {cnt} from {src} to {dst}
-                let mut parsed_step = step.split_whitespace();
-                let _ = parsed_step.next().unwrap();
-                let cnt = u32::from_str(parsed_step.next().unwrap()).unwrap();
-                let _ = parsed_step.next().unwrap();
-                let src = u32::from_str(parsed_step.next().unwrap()).unwrap();
-                let _ = parsed_step.next().unwrap();
-                let dst = u32::from_str(parsed_step.next().unwrap()).unwrap();
-                steps.push(Step { src, dst, cnt });
-            }
-            None => break,
+    for (idx, step) in raw_data {
+        let step = parse_step(step)
+            .ok_or(ParseStackError::InvalidStep { line: idx + 1 })?;
+        if !stack.contains_key(&step.src) || !stack.contains_key(&step.dst) {
+            return Err(ParseStackError::UnknownStack { line: idx + 1 });
         }
 
-        line = raw_data.next();
+        steps.push(step);
     }
 
     // Because of the way we parse, all stacks are backwards. We want "bottom"
     // elements to be at index 0 of the vector.
-    for (_, crates) in &mut stack {
+    for crates in stack.values_mut() {
         crates.reverse();
     }
 
-    (stack, steps)
+    Ok((stack, steps))
+}
+
+fn parse_step(step: &str) -> Option<Step> {
+    // move {cnt} from {src} to {dst}
+    match step.split_whitespace().collect::<Vec<&str>>()[..] {
+        ["move", cnt, "from", src, "to", dst] => Some(Step {
+            src: u32::from_str(src).ok()?,
+            dst: u32::from_str(dst).ok()?,
+            cnt: u32::from_str(cnt).ok()?,
+        }),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -134,7 +143,7 @@ mod test {
     use super::*;
 
     fn get_test_input() -> Vec<String> {
-        vec![
+        [
             "[x] [D] [x]",
             "[N] [C] [x] ",
             "[Z] [M] [P]",
@@ -152,7 +161,7 @@ mod test {
 
     #[test]
     fn test_parse_stack_and_steps() {
-        let (stack, steps) = parse_stack_and_steps(&get_test_input());
+        let (stack, steps) = parse_stack_and_steps(&get_test_input()).unwrap();
         for (stack_num, crates) in stack {
             println!("{}, {:?}", stack_num, crates);
         }
@@ -164,15 +173,68 @@ mod test {
 
     #[test]
     fn test_apply_steps_and_get_top_crates_part1() {
-        let (mut stack, steps) = parse_stack_and_steps(&get_test_input());
+        let (mut stack, steps) =
+            parse_stack_and_steps(&get_test_input()).unwrap();
         let result = apply_steps_and_get_top_crates(&mut stack, steps, false);
         assert_eq!(result, "CMZ");
     }
 
     #[test]
     fn test_apply_steps_and_get_top_crates_part2() {
-        let (mut stack, steps) = parse_stack_and_steps(&get_test_input());
+        let (mut stack, steps) =
+            parse_stack_and_steps(&get_test_input()).unwrap();
         let result = apply_steps_and_get_top_crates(&mut stack, steps, true);
         assert_eq!(result, "MCD");
     }
+
+    #[test]
+    fn test_parse_stack_and_steps_errors() {
+        let mut raw_data = get_test_input();
+        raw_data[1] = "[N] [C] [xx]".to_string();
+        assert_eq!(
+            parse_stack_and_steps(&raw_data).unwrap_err(),
+            ParseStackError::InvalidCrate { line: 2 }
+        );
+
+        let mut raw_data = get_test_input();
+        raw_data[6] = "move 3 from 1".to_string();
+        assert_eq!(
+            parse_stack_and_steps(&raw_data).unwrap_err(),
+            ParseStackError::InvalidStep { line: 7 }
+        );
+
+        let mut raw_data = get_test_input();
+        raw_data[6] = "move 3 from 1 to 4".to_string();
+        assert_eq!(
+            parse_stack_and_steps(&raw_data).unwrap_err(),
+            ParseStackError::UnknownStack { line: 7 }
+        );
+
+        for len in 0..4 {
+            assert_eq!(
+                parse_stack_and_steps(&get_test_input()[..len]).unwrap_err(),
+                ParseStackError::UnexpectedEnd
+            );
+        }
+        assert_eq!(
+            parse_stack_and_steps(&["".to_string()]).unwrap_err(),
+            ParseStackError::UnexpectedEnd
+        );
+    }
+
+    // Crash reproducers found by the parse_stack_and_steps fuzz target
+    #[test]
+    fn test_parse_stack_and_steps_fuzz_regressions() {
+        let parse = |lines: &[&str]| {
+            let raw_data: Vec<String> =
+                lines.iter().map(|x| x.to_string()).collect();
+            parse_stack_and_steps(&raw_data).unwrap_err()
+        };
+        assert_eq!(parse(&["["]), ParseStackError::InvalidCrate { line: 1 });
+        assert_eq!(parse(&["[A]"]), ParseStackError::UnexpectedEnd);
+        assert_eq!(
+            parse(&["[A]", " 1 ", "", "move"]),
+            ParseStackError::InvalidStep { line: 4 }
+        );
+    }
 }
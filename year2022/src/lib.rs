@@ -1,3 +1,4 @@
+use common::solution::{Solution, Tag};
 use std::fs;
 
 pub mod day01;
@@ -6,6 +7,49 @@ pub mod day03;
 pub mod day04;
 pub mod day05;
 
+pub const SOLUTIONS: &[Solution] = &[
+    Solution {
+        year: 2022,
+        day: 1,
+        title: "Calorie Counting",
+        tags: &[Tag::Parsing, Tag::Sorting],
+        answers: [Some("73211"), Some("213958")],
+        main: day01::main,
+    },
+    Solution {
+        year: 2022,
+        day: 2,
+        title: "Rock Paper Scissors",
+        tags: &[Tag::Parsing],
+        answers: [Some("13268"), Some("15508")],
+        main: day02::main,
+    },
+    Solution {
+        year: 2022,
+        day: 3,
+        title: "Rucksack Reorganization",
+        tags: &[Tag::Parsing, Tag::Sets],
+        answers: [Some("7742"), Some("2276")],
+        main: day03::main,
+    },
+    Solution {
+        year: 2022,
+        day: 4,
+        title: "Camp Cleanup",
+        tags: &[Tag::Parsing, Tag::Intervals],
+        answers: [Some("515"), Some("883")],
+        main: day04::main,
+    },
+    Solution {
+        year: 2022,
+        day: 5,
+        title: "Supply Stacks",
+        tags: &[Tag::Parsing, Tag::Simulation],
+        answers: [Some("CNSZFDVLJ"), Some("QNDWLMGNS")],
+        main: day05::main,
+    },
+];
+
 pub fn text_file_to_vec(path: &str) -> Vec<String> {
     let contents = fs::read_to_string(path).expect("Could not read file.");
 